@@ -71,14 +71,15 @@ where
 
 #[cfg(test)]
 mod tests {
-    use super::*;
+    use rdcl_aoc_core::include_input;
 
-    fn test_data() -> impl Iterator<Item = String> {
-        InputReader::from("./src/day02/test.txt").read_lines()
-    }
+    use super::*;
 
     #[test]
     fn test_check() {
-        assert_eq!(check(test_data()).unwrap(), (2, 1));
+        assert_eq!(
+            check(include_input!("test.txt").read_lines()).unwrap(),
+            (2, 1)
+        );
     }
 }
@@ -66,20 +66,18 @@ where
 
 #[cfg(test)]
 mod tests {
-    use super::*;
+    use rdcl_aoc_core::include_input;
 
-    fn test_data() -> impl Iterator<Item = String> {
-        InputReader::from("./src/day01/test.txt").read_lines()
-    }
+    use super::*;
 
     fn input() -> Vec<u32> {
-        parse_input(test_data()).unwrap()
+        parse_input(include_input!("test.txt").read_lines()).unwrap()
     }
 
     #[test]
     fn test_parse_input() {
         assert_eq!(
-            parse_input(test_data()).unwrap(),
+            parse_input(include_input!("test.txt").read_lines()).unwrap(),
             vec![6000, 4000, 11000, 24000, 10000]
         );
     }
@@ -61,14 +61,12 @@ fn compute_score(you: Choice, opponent: Choice) -> u32 {
 
 #[cfg(test)]
 mod tests {
-    use super::*;
+    use rdcl_aoc_core::include_input;
 
-    fn test_data() -> impl Iterator<Item = String> {
-        InputReader::from("./src/day02/test.txt").read_lines()
-    }
+    use super::*;
 
     #[test]
     fn test_play() {
-        assert_eq!(play(test_data()), (15, 12));
+        assert_eq!(play(include_input!("test.txt").read_lines()), (15, 12));
     }
 }
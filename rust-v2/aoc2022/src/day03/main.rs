@@ -116,10 +116,12 @@ impl FromStr for Rucksack {
 
 #[cfg(test)]
 mod tests {
+    use rdcl_aoc_core::include_input;
+
     use super::*;
 
     fn test_data() -> impl Iterator<Item = Rucksack> {
-        InputReader::from("./src/day03/test.txt").parse_lines(Rucksack::from_str)
+        include_input!("test.txt").parse_lines(Rucksack::from_str)
     }
 
     #[test]
@@ -47,10 +47,12 @@ where
 
 #[cfg(test)]
 mod tests {
+    use rdcl_aoc_core::include_input;
+
     use super::*;
 
     fn test_data() -> impl Iterator<Item = AssignmentPair> {
-        InputReader::from("./src/day04/test.txt").parse_lines(AssignmentPair::from_str)
+        include_input!("test.txt").parse_lines(AssignmentPair::from_str)
     }
 
     #[test]
@@ -147,15 +147,14 @@ where
 
 #[cfg(test)]
 mod tests {
-    use super::*;
+    use rdcl_aoc_core::include_input;
 
-    fn test_data() -> impl Iterator<Item = String> {
-        InputReader::from("./src/day05/test.txt").read_lines()
-    }
+    use super::*;
 
     #[test]
     fn test_parse_starting_configuration() {
-        let starting_configuration = parse_starting_configuration(&mut test_data()).unwrap();
+        let starting_configuration =
+            parse_starting_configuration(&mut include_input!("test.txt").read_lines()).unwrap();
 
         assert_eq!(
             starting_configuration,
@@ -165,7 +164,7 @@ mod tests {
 
     #[test]
     fn test_process_with_crate_mover_9000() {
-        let mut input = test_data();
+        let mut input = include_input!("test.txt").read_lines();
         let mut containers = parse_starting_configuration(&mut input).unwrap();
         process(&mut containers, &mut input, &mut CrateMover9000::default()).unwrap();
 
@@ -177,7 +176,7 @@ mod tests {
 
     #[test]
     fn test_process_with_crate_mover_9001() {
-        let mut input = test_data();
+        let mut input = include_input!("test.txt").read_lines();
         let mut containers = parse_starting_configuration(&mut input).unwrap();
         process(&mut containers, &mut input, &mut CrateMover9001::default()).unwrap();
 
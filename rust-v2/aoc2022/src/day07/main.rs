@@ -134,15 +134,14 @@ fn find_dir_to_delete(
 
 #[cfg(test)]
 mod tests {
-    use super::*;
+    use rdcl_aoc_core::include_input;
 
-    fn test_data() -> impl Iterator<Item = String> {
-        InputReader::from("./src/day07/test.txt").read_lines()
-    }
+    use super::*;
 
     #[test]
     fn test_compute_dir_sizes() {
-        let (root_dir_size, mut dir_sizes) = compute_dir_sizes(test_data()).unwrap();
+        let (root_dir_size, mut dir_sizes) =
+            compute_dir_sizes(include_input!("test.txt").read_lines()).unwrap();
         dir_sizes.sort_unstable();
 
         assert_eq!(root_dir_size, 48381165);
@@ -151,7 +150,8 @@ mod tests {
 
     #[test]
     fn test_find_dir_to_delete() {
-        let (root_dir_size, dir_sizes) = compute_dir_sizes(test_data()).unwrap();
+        let (root_dir_size, dir_sizes) =
+            compute_dir_sizes(include_input!("test.txt").read_lines()).unwrap();
         let dir_to_delete =
             find_dir_to_delete(&dir_sizes, root_dir_size, 70000000, 30000000).unwrap();
 
@@ -35,11 +35,12 @@ fn main() {
 #[cfg(test)]
 mod tests {
     use grid::grid;
+    use rdcl_aoc_core::include_input;
 
     use super::*;
 
     fn test_data() -> Forest {
-        InputReader::from("./src/day08/test.txt").parse::<Forest>()
+        include_input!("test.txt").parse::<Forest>()
     }
 
     fn forest() -> Forest {
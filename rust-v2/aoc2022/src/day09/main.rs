@@ -97,28 +97,31 @@ fn move_towards(tail: i32, head: i32) -> i32 {
 
 #[cfg(test)]
 mod tests {
-    use super::*;
-
-    fn test_data_1() -> impl Iterator<Item = String> {
-        InputReader::from("./src/day09/test-1.txt").read_lines()
-    }
+    use rdcl_aoc_core::include_input;
 
-    fn test_data_2() -> impl Iterator<Item = String> {
-        InputReader::from("./src/day09/test-2.txt").read_lines()
-    }
+    use super::*;
 
     #[test]
     fn test_travel_with_2_knots() {
-        assert_eq!(travel(test_data_1(), 2).unwrap(), 13);
+        assert_eq!(
+            travel(include_input!("test-1.txt").read_lines(), 2).unwrap(),
+            13
+        );
     }
 
     #[test]
     fn test_travel_with_10_knots_1() {
-        assert_eq!(travel(test_data_1(), 10).unwrap(), 1);
+        assert_eq!(
+            travel(include_input!("test-1.txt").read_lines(), 10).unwrap(),
+            1
+        );
     }
 
     #[test]
     fn test_travel_with_10_knots_2() {
-        assert_eq!(travel(test_data_2(), 10).unwrap(), 36);
+        assert_eq!(
+            travel(include_input!("test-2.txt").read_lines(), 10).unwrap(),
+            36
+        );
     }
 }
@@ -97,11 +97,10 @@ where
 
 #[cfg(test)]
 mod tests {
-    use super::*;
+    use rdcl_aoc_core::include_input;
+    use rdcl_aoc_ocr::render::render;
 
-    fn test_data() -> impl Iterator<Item = String> {
-        InputReader::from("./src/day10/test.txt").read_lines()
-    }
+    use super::*;
 
     #[test]
     fn test_run() {
@@ -117,7 +116,7 @@ mod tests {
         .to_string();
 
         assert_eq!(
-            run(test_data(), 40, 20, false).unwrap(),
+            run(include_input!("test.txt").read_lines(), 40, 20, false).unwrap(),
             (
                 expected_signal_strength,
                 expected_output,
@@ -125,4 +124,17 @@ mod tests {
             )
         );
     }
+
+    #[test]
+    fn test_output_ocr() {
+        let mut output = Output::new();
+        for line in render("EHZ FOR").lines() {
+            for pixel in line.chars() {
+                output.write(pixel == '#');
+            }
+            output.next_line();
+        }
+
+        assert_eq!(output.ocr(), "EHZ FOR");
+    }
 }
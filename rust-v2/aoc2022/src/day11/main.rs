@@ -60,15 +60,13 @@ fn compute_monkey_business(monkeys: &[Monkey], top_n: usize) -> usize {
 
 #[cfg(test)]
 mod tests {
-    use super::*;
+    use rdcl_aoc_core::include_input;
 
-    fn test_data() -> impl Iterator<Item = String> {
-        InputReader::from("./src/day11/test.txt").read_lines()
-    }
+    use super::*;
 
     #[test]
     fn test_compute_monkey_business_after_20_rounds_while_not_worried() {
-        let mut monkeys = parse(test_data()).unwrap();
+        let mut monkeys = parse(include_input!("test.txt").read_lines()).unwrap();
         for _ in 0..20 {
             play_round(&mut monkeys, false);
         }
@@ -77,7 +75,7 @@ mod tests {
 
     #[test]
     fn test_compute_monkey_business_after_10000_rounds_while_worried() {
-        let mut monkeys = parse(test_data()).unwrap();
+        let mut monkeys = parse(include_input!("test.txt").read_lines()).unwrap();
         for _ in 0..10000 {
             play_round(&mut monkeys, true);
         }
@@ -75,17 +75,13 @@ where
 
 #[cfg(test)]
 mod tests {
-    use rdcl_aoc_core::input::InputReader;
+    use rdcl_aoc_core::include_input;
 
     use super::*;
 
-    fn test_data() -> impl Iterator<Item = String> {
-        InputReader::from("./src/day11/test.txt").read_lines()
-    }
-
     #[test]
     fn test_play_rounds_while_not_worried() {
-        let mut monkeys = parse(test_data()).unwrap();
+        let mut monkeys = parse(include_input!("test.txt").read_lines()).unwrap();
         let mut round = 0;
 
         play_round(&mut monkeys, false);
@@ -117,7 +113,7 @@ mod tests {
 
     #[test]
     fn test_play_rounds_while_worried() {
-        let mut monkeys = parse(test_data()).unwrap();
+        let mut monkeys = parse(include_input!("test.txt").read_lines()).unwrap();
         let mut round = 0;
 
         play_round(&mut monkeys, true);
@@ -109,11 +109,9 @@ where
 
 #[cfg(test)]
 mod tests {
-    use super::*;
+    use rdcl_aoc_core::include_input;
 
-    fn test_data() -> impl Iterator<Item = String> {
-        InputReader::from("./src/day13/test.txt").read_lines()
-    }
+    use super::*;
 
     #[test]
     fn test_check_input() {
@@ -121,7 +119,7 @@ mod tests {
         let divider2 = "[[6]]".parse().unwrap();
 
         assert_eq!(
-            check_input(test_data(), divider1, divider2).unwrap(),
+            check_input(include_input!("test.txt").read_lines(), divider1, divider2).unwrap(),
             (vec![1, 2, 4, 6], 10, 14)
         );
     }
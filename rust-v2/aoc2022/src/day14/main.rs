@@ -167,11 +167,9 @@ fn draw_cave(rocks: &HashSet<Point>, sand: &HashSet<Point>) {
 
 #[cfg(test)]
 mod tests {
-    use super::*;
+    use rdcl_aoc_core::include_input;
 
-    fn test_data() -> impl Iterator<Item = String> {
-        InputReader::from("./src/day14/test.txt").read_lines()
-    }
+    use super::*;
 
     fn test_rocks() -> HashSet<Point> {
         HashSet::from([
@@ -226,7 +224,7 @@ mod tests {
 
     #[test]
     fn test_parse() {
-        let actual = parse(test_data(), Point(500, 0), 2).unwrap();
+        let actual = parse(include_input!("test.txt").read_lines(), Point(500, 0), 2).unwrap();
         let expected = (test_rocks(), 9);
 
         assert_eq!(actual, expected);
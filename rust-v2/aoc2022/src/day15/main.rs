@@ -128,10 +128,12 @@ fn find_distress_beacon(sensors: &[Sensor], lower_bound: i64, upper_bound: i64)
 
 #[cfg(test)]
 mod tests {
+    use rdcl_aoc_core::include_input;
+
     use super::*;
 
     fn test_data() -> Vec<Sensor> {
-        InputReader::from("./src/day15/test.txt")
+        include_input!("test.txt")
             .parse_lines(Sensor::from_str)
             .collect()
     }
@@ -1,8 +1,8 @@
 use std::fmt;
 use std::str::FromStr;
 
-use rdcl_aoc_core::err_parse_error;
 use rdcl_aoc_core::error::ParseError;
+use rdcl_aoc_core::parse_pattern;
 use rdcl_aoc_pathfinding::taxi_cab_2d;
 
 use crate::line::Line;
@@ -48,34 +48,14 @@ impl FromStr for Sensor {
     type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let sub = match s.strip_prefix("Sensor at ") {
-            Some(s) => s,
-            None => {
-                return err_parse_error!("Cannot parse sensor: {}", s);
-            }
-        };
-
-        let i = match sub.find(": ") {
-            Some(i) => i,
-            None => {
-                return err_parse_error!("Cannot parse sensor: {}", s);
-            }
-        };
-
-        let coordinate = sub[..i].parse::<Point>()?;
-
-        let sub = match sub[i..].strip_prefix(": closest beacon is at ") {
-            Some(s) => s,
-            None => {
-                return err_parse_error!("Cannot parse beacon: {}", s);
-            }
-        };
-
-        let closest_beacon = sub.parse::<Point>()?;
+        let (sx, sy, bx, by) = parse_pattern!(
+            s,
+            "Sensor at x={}, y={}: closest beacon is at x={}, y={}" => i64, i64, i64, i64
+        )?;
 
         Ok(Sensor {
-            coordinate,
-            closest_beacon,
+            coordinate: Point(sx, sy),
+            closest_beacon: Point(bx, by),
         })
     }
 }
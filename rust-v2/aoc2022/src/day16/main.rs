@@ -57,10 +57,12 @@ fn main() {
 
 #[cfg(test)]
 mod tests {
+    use rdcl_aoc_core::include_input;
+
     use super::*;
 
     fn test_data() -> Volcano {
-        InputReader::from("./src/day16/test.txt").parse()
+        include_input!("test.txt").parse()
     }
 
     #[test]
@@ -78,4 +80,28 @@ mod tests {
             1707
         );
     }
+
+    #[test]
+    fn test_too_many_useful_valves() {
+        let label = |i: u8| format!("{}{}", (b'A' + i / 26) as char, (b'A' + i % 26) as char);
+        let input = (0..65)
+            .map(|i| {
+                let next = label((i + 1) % 65);
+                format!(
+                    "Valve {} has flow rate=1; tunnel leads to valve {}",
+                    label(i),
+                    next
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        let err = InputReader::from_text(input)
+            .try_parse::<Volcano>()
+            .unwrap_err();
+        assert_eq!(
+            err.message(),
+            "Found 65 valves with a positive flow rate, but at most 64 are supported"
+        );
+    }
 }
@@ -3,31 +3,35 @@ use std::collections::{HashMap, HashSet};
 use rdcl_aoc_core::err_parse_error;
 use rdcl_aoc_core::error::ParseError;
 use rdcl_aoc_core::input::FromInput;
+use rdcl_aoc_pathfinding::branch_and_bound::BranchAndBound;
 use rdcl_aoc_pathfinding::AStar;
 
 use crate::label::Label;
 use crate::tunnels::Tunnels;
 use crate::valve::Valve;
 
+/// The maximum number of valves with a positive flow rate.
+const MAX_USEFUL_VALVES: usize = u64::BITS as usize;
+
 #[derive(Debug, Default)]
 pub struct Volcano {
     valves: HashMap<Label, Valve>,
-    useful_valves: HashMap<Label, Valve>,
+    useful_valves: Vec<Valve>,
     connections: HashMap<(Label, Label), usize>,
 }
 
 impl Volcano {
     pub fn find_max_pressure_relief(&self, start: Label, time_remaining: usize) -> usize {
-        let mut best = 0;
-        self.find(
-            (start, time_remaining),
-            (start, 0),
-            &mut best,
-            self.useful_valves.len(),
-            0,
-            &mut HashSet::new(),
-        );
-        best
+        let expedition = Expedition {
+            you: (start, time_remaining),
+            elephant: (start, 0),
+            division: self.useful_valves.len(),
+            relieved: 0,
+            open: 0,
+        };
+
+        self.maximise([expedition])
+            .map_or(0, |solution| solution.score)
     }
 
     pub fn find_max_pressure_relief_with_elephant(
@@ -35,137 +39,30 @@ impl Volcano {
         start: Label,
         time_remaining: usize,
     ) -> usize {
-        let mut best = 0;
-        for division in (0..=self.useful_valves.len() / 2).rev() {
-            let result = self.find(
-                (start, time_remaining),
-                (start, time_remaining),
-                &mut best,
-                division,
-                0,
-                &mut HashSet::new(),
-            );
-            println!("{} -- {}", division, result);
-        }
-        best
-    }
-
-    /// Work together with an elephant to find the optimal strategy.
-    ///
-    /// You will start picking which valves you are going to open, and the elephant will open the remaining valves.
-    /// The parameter `division` indicates how the work is divided.
-    /// This number indicates how many valves should be opened by you.
-    ///
-    /// * `you` - Your current position and how much time there is left.
-    /// * `elephant` - The current position of the elephant and how much time there is left.
-    /// * `division` - How many valves should `you` open?
-    /// * `relieved` - How much pressure has been relieved so far.
-    /// * `valves` - The valves which are relevant (i.e. do not have a flow rate of 0).
-    /// * `open` - Which valves are currently open.
-    fn find(
-        &self,
-        you: (Label, usize),
-        elephant: (Label, usize),
-        best_so_far: &mut usize,
-        division: usize,
-        relieved: usize,
-        open: &mut HashSet<Label>,
-    ) -> usize {
-        let (start, time_remaining) = if division > 0 { you } else { elephant };
-
-        // Start by applying a heuristic. Compute an upper bound for the best possible result from
-        // the current situation. If this upper bound is lower than the best score that was found so
-        // far, then there is no point continuing down this branch.
-        let upper_bound = self.compute_upper_bound_for_relief(you, elephant, open, division);
-        if relieved + upper_bound < *best_so_far {
-            // no point going any further
-            return 0;
-        }
-
-        // Determine which valves can still be opened, given the remaining time.
-        let mut valves: Vec<(Label, Valve, usize)> = self
-            .useful_valves
-            .iter()
-            .filter(|(label, _)| !open.contains(label))
-            .map(|(&label, &valve)| {
-                (
-                    label,
-                    valve,
-                    *self.connections.get(&(start, label)).unwrap(),
-                )
-            })
-            .filter(|(_, _, distance)| *distance < time_remaining)
-            .collect();
-
-        // Sort the valves by the highest possible relief they can provide. Try the valves with the
-        // highest relief first.
-        valves.sort_unstable_by_key(|(_, valve, distance)| {
-            valve.flow_rate() * (time_remaining - *distance)
+        let expeditions = (0..=self.useful_valves.len() / 2).map(|division| Expedition {
+            you: (start, time_remaining),
+            elephant: (start, time_remaining),
+            division,
+            relieved: 0,
+            open: 0,
         });
 
-        let mut best = relieved;
-        for (label, valve, distance) in valves.into_iter().rev() {
-            open.insert(label);
-            let time_remaining = time_remaining - distance;
-            let relieved = relieved + time_remaining * valve.flow_rate();
-
-            best = best.max(if division > 0 {
-                self.find(
-                    (label, time_remaining),
-                    elephant,
-                    best_so_far,
-                    division - 1,
-                    relieved,
-                    open,
-                )
-            } else {
-                self.find(
-                    you,
-                    (label, time_remaining),
-                    best_so_far,
-                    division,
-                    relieved,
-                    open,
-                )
-            });
-            *best_so_far = (*best_so_far).max(best);
-
-            open.remove(&label);
-        }
-        best
+        self.maximise(expeditions)
+            .map_or(0, |solution| solution.score)
     }
 
     /// Upper bound for the amount of pressure that could possibly be relieved.
-    fn compute_upper_bound_for_relief(
-        &self,
-        you: (Label, usize),
-        elephant: (Label, usize),
-        open: &HashSet<Label>,
-        division: usize,
-    ) -> usize {
+    fn compute_upper_bound_for_relief(&self, expedition: &Expedition) -> usize {
         let mut score = 0;
 
-        for (&label, &valve) in &self.useful_valves {
-            if !open.contains(&label) {
-                let score_you = if division > 0 {
-                    let (position, time_remaining) = you;
-                    let distance = *self.connections.get(&(position, label)).unwrap();
-                    if distance < time_remaining {
-                        valve.flow_rate() * (time_remaining - distance)
-                    } else {
-                        0
-                    }
-                } else {
-                    0
-                };
-
-                let (position, time_remaining) = elephant;
-                let distance = *self.connections.get(&(position, label)).unwrap();
-                let score_elephant = if distance < time_remaining {
-                    valve.flow_rate() * (time_remaining - distance)
+        for (i, valve) in self.useful_valves.iter().enumerate() {
+            if !expedition.is_open(i) {
+                let score_you = if expedition.division > 0 {
+                    self.relief(expedition.you, valve)
                 } else {
                     0
                 };
+                let score_elephant = self.relief(expedition.elephant, valve);
 
                 score += score_you.max(score_elephant);
             }
@@ -173,6 +70,105 @@ impl Volcano {
 
         score
     }
+
+    /// How much pressure would be relieved by walking from `position` to `valve` and opening it.
+    fn relief(&self, (position, time_remaining): (Label, usize), valve: &Valve) -> usize {
+        let distance = self.connections[&(position, valve.label())];
+        if distance < time_remaining {
+            valve.flow_rate() * (time_remaining - distance)
+        } else {
+            0
+        }
+    }
+}
+
+/// Work together with an elephant to find the optimal strategy.
+///
+/// You will start picking which valves you are going to open, and the elephant will open the remaining valves.
+/// The field `division` indicates how the work is divided.
+/// This number indicates how many valves should still be opened by you.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Expedition {
+    /// Your current position and how much time there is left.
+    you: (Label, usize),
+    /// The current position of the elephant and how much time there is left.
+    elephant: (Label, usize),
+    /// How many valves should `you` still open?
+    division: usize,
+    /// How much pressure has been relieved so far.
+    relieved: usize,
+    /// Which of the useful valves are currently open.
+    open: u64,
+}
+
+impl Expedition {
+    fn is_open(&self, valve: usize) -> bool {
+        self.open & (1 << valve) != 0
+    }
+}
+
+impl BranchAndBound for Volcano {
+    type State = Expedition;
+    type Score = usize;
+    type Key = ((Label, usize), (Label, usize), usize, u64);
+
+    fn children(&self, expedition: &Expedition) -> Vec<Expedition> {
+        let (position, time_remaining) = if expedition.division > 0 {
+            expedition.you
+        } else {
+            expedition.elephant
+        };
+
+        // Determine which valves can still be opened, given the remaining time.
+        let mut children = vec![];
+        for (i, valve) in self.useful_valves.iter().enumerate() {
+            if expedition.is_open(i) {
+                continue;
+            }
+            let distance = self.connections[&(position, valve.label())];
+            if distance >= time_remaining {
+                continue;
+            }
+
+            let time_remaining = time_remaining - distance;
+            let mut next = Expedition {
+                relieved: expedition.relieved + time_remaining * valve.flow_rate(),
+                open: expedition.open | (1 << i),
+                ..*expedition
+            };
+            if expedition.division > 0 {
+                next.you = (valve.label(), time_remaining);
+                next.division -= 1;
+            } else {
+                next.elephant = (valve.label(), time_remaining);
+            }
+            children.push(next);
+        }
+        children
+    }
+
+    fn upper_bound(&self, expedition: &Expedition) -> usize {
+        expedition.relieved + self.compute_upper_bound_for_relief(expedition)
+    }
+
+    fn score(&self, expedition: &Expedition) -> Option<usize> {
+        Some(expedition.relieved)
+    }
+
+    fn key(&self, expedition: &Expedition) -> Option<Self::Key> {
+        Some((
+            expedition.you,
+            expedition.elephant,
+            expedition.division,
+            expedition.open,
+        ))
+    }
+
+    /// If both expeditions are in the same situation, then the one that has relieved the most
+    /// pressure so far is the best.
+    fn dominates(&self, a: &Expedition, b: &Expedition) -> bool {
+        a.relieved >= b.relieved
+    }
 }
 
 impl FromInput for Volcano {
@@ -196,7 +192,7 @@ impl FromInput for Volcano {
             labels.push(valve.label());
             volcano.valves.insert(valve.label(), valve);
             if valve.flow_rate() > 0 {
-                volcano.useful_valves.insert(valve.label(), valve);
+                volcano.useful_valves.push(valve);
             }
 
             let sub = match line[i + 2..].strip_prefix("tunnels lead to valves ") {
@@ -216,6 +212,15 @@ impl FromInput for Volcano {
             tunnels.insert(valve.label(), targets);
         }
 
+        // which of the useful valves are open is tracked in a bitset
+        if volcano.useful_valves.len() > MAX_USEFUL_VALVES {
+            return err_parse_error!(
+                "Found {} valves with a positive flow rate, but at most {} are supported",
+                volcano.useful_valves.len(),
+                MAX_USEFUL_VALVES
+            );
+        }
+
         for (i, label1) in labels.iter().enumerate() {
             for label2 in labels.iter().skip(i + 1) {
                 let distance = tunnels.find_shortest_path(label1, label2).unwrap();
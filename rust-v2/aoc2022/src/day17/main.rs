@@ -1,10 +1,10 @@
 //! The solution for [advent of code 2022, day 17](https://adventofcode.com/2022/day/17)
 
-use std::collections::HashMap;
 use std::path::PathBuf;
 
 use clap::Parser;
 
+use rdcl_aoc_core::cycle::simulate_with_cycle_skip_by_key;
 use rdcl_aoc_core::input::InputReader;
 
 use crate::chamber::Chamber;
@@ -44,48 +44,41 @@ fn main() {
     );
 }
 
-fn simulate(mut chamber: Chamber, input: String, mut rock_count: usize) -> usize {
-    let mut wind_iter = input.chars();
-    let mut seen: HashMap<String, (usize, usize)> = HashMap::new();
-    let mut extra_height = 0;
-
-    while chamber.fallen_rocks_count() < rock_count {
-        let wind = match wind_iter.next() {
-            Some(wind) => wind,
-            None => {
-                if extra_height == 0 {
-                    // check if we are looping
-                    let summary = chamber.summarize();
-                    if let Some((prev_height, prev_rocks)) = seen.get(&summary) {
-                        // loop detected
-                        let period = chamber.fallen_rocks_count() - prev_rocks;
-                        let remaining_rocks = rock_count - chamber.fallen_rocks_count();
-                        let d_height = chamber.height() - prev_height;
-
-                        extra_height = (remaining_rocks / period) * d_height;
-                        rock_count = chamber.fallen_rocks_count() + remaining_rocks % period;
-                    }
-                    seen.insert(summary, (chamber.height(), chamber.fallen_rocks_count()));
-                }
-
-                // reset the iterator
-                wind_iter = input.chars();
-                wind_iter.next().unwrap()
-            }
-        };
-
-        chamber.step(wind);
+fn simulate(chamber: Chamber, input: String, rock_count: usize) -> usize {
+    let winds: Vec<char> = input.chars().collect();
+    let mut heights = vec![chamber.height()];
+
+    let drop_rock = |(mut chamber, mut wind): (Chamber, usize)| {
+        let fallen = chamber.fallen_rocks_count();
+        while chamber.fallen_rocks_count() == fallen {
+            chamber.step(winds[wind]);
+            wind = (wind + 1) % winds.len();
+        }
+        heights.push(chamber.height());
+        (chamber, wind)
+    };
+    let skip = simulate_with_cycle_skip_by_key((chamber, 0), drop_rock, rock_count, |(c, w)| {
+        (*w, c.summarize())
+    });
+
+    let (chamber, _) = skip.state;
+    match skip.cycle {
+        Some(cycle) => {
+            let d_height = heights[cycle.start + cycle.length] - heights[cycle.start];
+            chamber.height() + skip.skipped * d_height
+        }
+        None => chamber.height(),
     }
-
-    extra_height + chamber.height()
 }
 
 #[cfg(test)]
 mod tests {
+    use rdcl_aoc_core::include_input;
+
     use super::*;
 
     fn test_data() -> String {
-        InputReader::from("./src/day17/test.txt").read_line()
+        include_input!("test.txt").read_line()
     }
 
     #[test]
@@ -124,10 +124,12 @@ fn surface_area(regions: &[Region]) -> usize {
 
 #[cfg(test)]
 mod tests {
+    use rdcl_aoc_core::include_input;
+
     use super::*;
 
     fn test_data() -> Vec<Point> {
-        InputReader::from("./src/day18/test.txt")
+        include_input!("test.txt")
             .parse_lines(Point::from_str)
             .collect()
     }
@@ -2,8 +2,8 @@ use core::fmt;
 use std::ops::{Add, Sub};
 use std::str::FromStr;
 
-use rdcl_aoc_core::err_parse_error;
 use rdcl_aoc_core::error::ParseError;
+use rdcl_aoc_core::parser::parse_separated_array;
 
 #[derive(Copy, Clone, Eq, PartialEq, Hash)]
 pub struct Point {
@@ -79,28 +79,7 @@ impl FromStr for Point {
     type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut iter = s.split(',');
-
-        let x = match iter.next() {
-            Some(v) => v.parse()?,
-            None => {
-                return err_parse_error!("Invalid input: {}", s);
-            }
-        };
-
-        let y = match iter.next() {
-            Some(v) => v.parse()?,
-            None => {
-                return err_parse_error!("Invalid input: {}", s);
-            }
-        };
-
-        let z = match iter.next() {
-            Some(v) => v.parse()?,
-            None => {
-                return err_parse_error!("Invalid input: {}", s);
-            }
-        };
+        let [x, y, z] = parse_separated_array(s, ",")?;
 
         Ok(Point { x, y, z })
     }
@@ -59,12 +59,12 @@ impl FromStr for Blueprint {
 
 #[cfg(test)]
 pub mod tests {
-    use rdcl_aoc_core::input::InputReader;
+    use rdcl_aoc_core::include_input;
 
     use super::*;
 
     pub fn test_data() -> Vec<Blueprint> {
-        InputReader::from("./src/day19/test.txt")
+        include_input!("test.txt")
             .parse_lines(Blueprint::from_str)
             .collect()
     }
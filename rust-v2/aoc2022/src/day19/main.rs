@@ -1,12 +1,12 @@
 //! The solution for [advent of code 2022, day 19](https://adventofcode.com/2022/day/19)
 
-use std::cmp::Ordering;
 use std::path::PathBuf;
 use std::str::FromStr;
 
 use clap::Parser;
 
 use rdcl_aoc_core::input::InputReader;
+use rdcl_aoc_pathfinding::branch_and_bound::BranchAndBound;
 
 use crate::blueprint::Blueprint;
 use crate::pool::Pool;
@@ -46,31 +46,9 @@ fn eval(blueprint: &Blueprint, minutes: u32) -> u32 {
         time_left: minutes,
     };
 
-    // let mut open_set: BinaryHeap<State> = BinaryHeap::new();
-    let mut open_set: Vec<State> = Vec::new();
-    open_set.push(initial_state);
-
-    let mut best = 0;
-    while let Some(state) = open_set.pop() {
-        let State { pool, time_left } = state;
-        if time_left == 0 {
-            best = best.max(pool.resources.geode);
-            continue;
-        }
-
-        let potential = pool.potential(time_left);
-        if potential == 0 || potential <= best {
-            continue;
-        }
-
-        for pool in pool.next_pools(blueprint) {
-            let next_state = State {
-                pool,
-                time_left: time_left - 1,
-            };
-            open_set.push(next_state);
-        }
-    }
+    let best = Factory(blueprint)
+        .maximise_parallel([initial_state])
+        .map_or(0, |solution| solution.score);
 
     println!(
         "Blueprint #{} is able to produce {best} geodes, giving it a quality score of {}.",
@@ -87,25 +65,36 @@ struct State {
     time_left: u32,
 }
 
-impl Ord for State {
-    fn cmp(&self, other: &Self) -> Ordering {
-        let p1 = self.pool.potential(self.time_left);
-        let p2 = other.pool.potential(other.time_left);
-
-        p1.cmp(&p2)
-            .then(
-                self.pool
-                    .resources
-                    .obsidian
-                    .cmp(&other.pool.resources.obsidian),
-            )
-            .then(self.pool.resources.clay.cmp(&other.pool.resources.clay))
-            .reverse()
+/// The robot factory, which builds robots according to a blueprint.
+struct Factory<'a>(&'a Blueprint);
+
+impl BranchAndBound for Factory<'_> {
+    type State = State;
+    type Score = u32;
+    type Key = ();
+
+    fn children(&self, state: &State) -> Vec<State> {
+        if state.time_left == 0 {
+            return vec![];
+        }
+
+        state
+            .pool
+            .next_pools(self.0)
+            .into_iter()
+            .map(|pool| State {
+                pool,
+                time_left: state.time_left - 1,
+            })
+            .collect()
+    }
+
+    fn upper_bound(&self, state: &State) -> u32 {
+        state.pool.potential(state.time_left)
     }
-}
 
-impl PartialOrd for State {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
+    /// The number of geodes that will have been mined if no more robots are built.
+    fn score(&self, state: &State) -> Option<u32> {
+        Some(state.pool.resources.geode + state.pool.robots.geode * state.time_left)
     }
 }
@@ -92,10 +92,12 @@ fn grove_coordinates(input: &[i64]) -> (i64, i64, i64) {
 
 #[cfg(test)]
 mod tests {
+    use rdcl_aoc_core::include_input;
+
     use super::*;
 
     fn test_data() -> Vec<i64> {
-        InputReader::from("./src/day20/test.txt")
+        include_input!("test.txt")
             .parse_lines(|l| l.parse::<i64>())
             .collect()
     }
@@ -136,10 +136,12 @@ fn fix_input(monkeys: &mut HashMap<String, Monkey>, root: &str, you: &str) {
 
 #[cfg(test)]
 mod tests {
+    use rdcl_aoc_core::include_input;
+
     use super::*;
 
     fn test_data() -> HashMap<String, Monkey> {
-        parse(InputReader::from("./src/day21/test.txt").read_lines()).unwrap()
+        parse(include_input!("test.txt").read_lines()).unwrap()
     }
 
     #[test]
@@ -84,21 +84,14 @@ where
 
 #[cfg(test)]
 mod tests {
-    use super::*;
-
-    fn test_data() -> impl Iterator<Item = String> {
-        InputReader::from("./src/day22/test.txt").read_lines()
-    }
+    use rdcl_aoc_core::include_input;
 
-    /// Contains the same data as `test_data`, but transformed in such a way that it matches the
-    /// expected input shape.
-    fn test_data_alt() -> impl Iterator<Item = String> {
-        InputReader::from("./src/day22/test-alt.txt").read_lines()
-    }
+    use super::*;
 
     #[test]
     fn test_password() {
-        let (mut grove, instructions) = parse(test_data(), false).unwrap();
+        let (mut grove, instructions) =
+            parse(include_input!("test.txt").read_lines(), false).unwrap();
         grove.follow_instructions(instructions);
 
         assert_eq!(grove.password(), 6032);
@@ -106,7 +99,10 @@ mod tests {
 
     #[test]
     fn test_password_cube() {
-        let (mut grove, instructions) = parse(test_data_alt(), true).unwrap();
+        // `test-alt.txt` contains the same data as `test.txt`, but transformed in such a way that
+        // it matches the expected input shape.
+        let (mut grove, instructions) =
+            parse(include_input!("test-alt.txt").read_lines(), true).unwrap();
         grove.follow_instructions(instructions);
 
         assert_eq!(grove.password(), 10006);
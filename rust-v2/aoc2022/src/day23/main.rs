@@ -158,10 +158,12 @@ where
 
 #[cfg(test)]
 mod tests {
+    use rdcl_aoc_core::include_input;
+
     use super::*;
 
     fn test_data() -> HashSet<(i64, i64)> {
-        parse(InputReader::from("./src/day23/test.txt").read_lines())
+        parse(include_input!("test.txt").read_lines())
     }
 
     #[test]
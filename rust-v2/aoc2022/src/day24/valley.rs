@@ -214,12 +214,12 @@ impl fmt::Display for Valley {
 
 #[cfg(test)]
 pub mod tests {
-    use rdcl_aoc_core::input::InputReader;
+    use rdcl_aoc_core::include_input;
 
     use super::*;
 
     pub fn test_data() -> Valley {
-        InputReader::from("./src/day24/test.txt").parse::<Valley>()
+        include_input!("test.txt").parse::<Valley>()
     }
 
     #[test]
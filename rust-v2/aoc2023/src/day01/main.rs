@@ -93,25 +93,19 @@ fn matches_digit(line: &str, digit: &str, start: usize) -> bool {
 
 #[cfg(test)]
 mod tests {
-    use super::*;
-
-    fn test_data_1() -> impl Iterator<Item = String> {
-        InputReader::from("./src/day01/test1.txt").read_lines()
-    }
+    use rdcl_aoc_core::include_input;
 
-    fn test_data_2() -> impl Iterator<Item = String> {
-        InputReader::from("./src/day01/test2.txt").read_lines()
-    }
+    use super::*;
 
     #[test]
     fn test_part_1() {
-        let (sum, _) = solve(test_data_1()).unwrap();
+        let (sum, _) = solve(include_input!("test1.txt").read_lines()).unwrap();
         assert_eq!(sum, 142);
     }
 
     #[test]
     fn test_part_2() {
-        let (_, sum) = solve(test_data_2()).unwrap();
+        let (_, sum) = solve(include_input!("test2.txt").read_lines()).unwrap();
         assert_eq!(sum, 281);
     }
 }
@@ -45,10 +45,12 @@ fn main() -> MainResult {
 
 #[cfg(test)]
 mod tests {
+    use rdcl_aoc_core::include_input;
+
     use super::*;
 
     fn test_data() -> impl Iterator<Item = Game> {
-        InputReader::from("./src/day02/test.txt").parse_lines(Game::from_str)
+        include_input!("test.txt").parse_lines(Game::from_str)
     }
 
     #[test]
@@ -180,12 +180,12 @@ fn check_digit(schematics: &[String], row: usize, col: usize) -> Option<u32> {
 
 #[cfg(test)]
 mod tests {
+    use rdcl_aoc_core::include_input;
+
     use super::*;
 
     fn test_data() -> Vec<String> {
-        InputReader::from("./src/day03/test.txt")
-            .read_lines()
-            .collect()
+        include_input!("test.txt").read_lines().collect()
     }
 
     #[test]
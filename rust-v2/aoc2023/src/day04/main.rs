@@ -58,10 +58,12 @@ fn evaluate(cards: &[ScratchCard]) -> Vec<usize> {
 
 #[cfg(test)]
 mod tests {
+    use rdcl_aoc_core::include_input;
+
     use super::*;
 
     fn test_data() -> Vec<ScratchCard> {
-        InputReader::from("./src/day04/test.txt")
+        include_input!("test.txt")
             .parse_lines(ScratchCard::from_str)
             .collect()
     }
@@ -2,9 +2,7 @@ use std::collections::HashSet;
 use std::str::FromStr;
 
 use rdcl_aoc_core::error::ParseError;
-use rdcl_aoc_core::parser::{
-    parse_whitespace_separated_to_hashset, parse_whitespace_separated_to_vec,
-};
+use rdcl_aoc_core::parser::parse_separated;
 
 #[derive(Debug, Clone)]
 pub struct ScratchCard {
@@ -49,8 +47,8 @@ impl FromStr for ScratchCard {
         let pos2 = line.find('|').ok_or(())?;
 
         let id = line[..pos1].parse::<usize>()?;
-        let winning = parse_whitespace_separated_to_hashset(&line[pos1 + 1..pos2])?;
-        let yours = parse_whitespace_separated_to_vec(&line[pos2 + 1..])?;
+        let winning = parse_separated(&line[pos1 + 1..pos2], " ")?;
+        let yours = parse_separated(&line[pos2 + 1..], " ")?;
 
         Ok(ScratchCard { id, winning, yours })
     }
@@ -82,15 +82,13 @@ fn find_min(ranges: &[(u64, u64)]) -> Option<u64> {
 
 #[cfg(test)]
 mod tests {
-    use super::*;
+    use rdcl_aoc_core::include_input;
 
-    fn test_data() -> impl Iterator<Item = String> {
-        InputReader::from("./src/day05/test.txt").read_lines()
-    }
+    use super::*;
 
     #[test]
     fn test_evaluate_mappings_naive() {
-        let mut input = test_data().peekable();
+        let mut input = include_input!("test.txt").read_lines().peekable();
         let (seeds, _) = parse_seeds(&mut input).unwrap();
         let mappings = <Vec<Mapping>>::parse(&mut input).unwrap();
         let locations = evaluate_seeds(&seeds, &mappings);
@@ -100,7 +98,7 @@ mod tests {
 
     #[test]
     fn test_evaluate_mappings_full() {
-        let mut input = test_data().peekable();
+        let mut input = include_input!("test.txt").read_lines().peekable();
         let (_, seeds) = parse_seeds(&mut input).unwrap();
         let mappings = <Vec<Mapping>>::parse(&mut input).unwrap();
         let mut locations = evaluate_seed_ranges(&seeds, &mappings);
@@ -61,10 +61,12 @@ fn parse_line(line: &str) -> ParseResult<InputLine> {
 
 #[cfg(test)]
 mod tests {
+    use rdcl_aoc_core::include_input;
+
     use super::*;
 
     fn test_data() -> Vec<InputLine> {
-        InputReader::from("./src/day07/test.txt")
+        include_input!("test.txt")
             .parse_lines(parse_line)
             .collect::<Vec<InputLine>>()
     }
@@ -7,7 +7,7 @@ use clap::Parser;
 
 use rdcl_aoc_core::input::InputReader;
 use rdcl_aoc_core::{assert_or_parse_error, err_parse_error, MainResult, ParseResult};
-use rdcl_aoc_math::lcm;
+use rdcl_aoc_math::solve_crt_system;
 
 use crate::map::{Direction, Label, Node};
 
@@ -79,63 +79,108 @@ fn follow_ghostly_instructions(
     from: char,
     to: char,
 ) -> Option<usize> {
-    let starting_positions = map
+    let mut current = map
         .keys()
         .filter(|key| key.ends_with(from))
         .cloned()
         .collect::<Vec<_>>();
 
     let mut steps = 0;
-    let mut current = starting_positions.clone();
     let mut index = 0;
 
-    // in order to reduce run time, keep track of loops that have been encountered
-    let mut detected_loops: Vec<Option<usize>> = vec![None; starting_positions.len()];
+    // in order to reduce run time, keep track of the route of each ghost until it repeats itself
+    let mut ghosts: Vec<Ghost> = current.iter().map(|_| Ghost::default()).collect();
 
-    while !is_done(&current, &detected_loops, &mut steps, to) {
-        let mut next = Vec::with_capacity(current.len());
-        steps += 1;
-
-        for (i, label) in current.iter().enumerate() {
-            let next_node = map.get(label)?.travel(instructions[index]);
-            next.push(next_node.clone());
+    loop {
+        if current.iter().all(|label| label.ends_with(to)) {
+            return Some(steps);
+        }
 
-            // if the next node is the same as the start node, then a loop has been detected
-            let l = detected_loops.get_mut(i)?;
-            if l.is_none() && next_node.ends_with(to) {
-                *l = Some(steps);
-            }
+        for (ghost, label) in ghosts.iter_mut().zip(&current) {
+            ghost.visit(label, index, steps, to);
+        }
+        if ghosts.iter().all(|ghost| ghost.cycle.is_some()) {
+            return solve_cycles(&ghosts, steps);
         }
 
+        steps += 1;
+        current = current
+            .iter()
+            .map(|label| map[label].travel(instructions[index]))
+            .collect();
         index = (index + 1) % instructions.len();
-        current = next;
     }
+}
 
-    Some(steps)
+/// The route of a single ghost, which is tracked until it starts repeating itself.
+#[derive(Debug, Default)]
+struct Ghost {
+    /// The step at which the ghost first visited a node at a given instruction index.
+    seen: HashMap<(Label, usize), usize>,
+    /// The steps at which the ghost reached its goal.
+    arrivals: Vec<usize>,
+    /// The step at which the cycle starts, and its length.
+    cycle: Option<(usize, usize)>,
 }
 
-/// Check if all necessary calculations are done.
-///
-/// If a loop has been detected for every single path, then the entire
-/// process can be short-circuited.
-fn is_done(
-    labels: &[Label],
-    detected_loops: &[Option<usize>],
-    steps: &mut usize,
-    to: char,
-) -> bool {
-    let all_loops_detected = detected_loops.iter().all(|o| o.is_some());
-    if all_loops_detected {
-        *steps = detected_loops
+impl Ghost {
+    fn visit(&mut self, label: &Label, index: usize, steps: usize, to: char) {
+        if self.cycle.is_some() {
+            return;
+        }
+
+        match self.seen.get(&(label.clone(), index)) {
+            Some(&start) => self.cycle = Some((start, steps - start)),
+            None => {
+                self.seen.insert((label.clone(), index), steps);
+                if label.ends_with(to) {
+                    self.arrivals.push(steps);
+                }
+            }
+        }
+    }
+}
+
+/// Short-circuits the process once every ghost is in its cycle, by solving the systems of
+/// congruences for every combination of goals that the ghosts reach within their cycles. Only
+/// solutions after `steps` are considered, since the ghosts have already been followed that far.
+/// Returns `None` if the ghosts will never reach their goals at the same time.
+fn solve_cycles(ghosts: &[Ghost], steps: usize) -> Option<usize> {
+    let mut systems: Vec<Vec<(u64, u64)>> = vec![vec![]];
+    for ghost in ghosts {
+        let (start, length) = ghost.cycle?;
+        let congruences: Vec<(u64, u64)> = ghost
+            .arrivals
             .iter()
-            .map(|o| o.unwrap())
-            .reduce(lcm)
-            .unwrap();
+            .filter(|&&arrival| arrival >= start)
+            .map(|&arrival| (length as u64, arrival as u64))
+            .collect();
 
-        true
-    } else {
-        labels.iter().all(|label| label.ends_with(to))
+        systems = systems
+            .iter()
+            .flat_map(|system| {
+                congruences.iter().map(|&congruence| {
+                    let mut system = system.clone();
+                    system.push(congruence);
+                    system
+                })
+            })
+            .collect();
     }
+
+    systems
+        .into_iter()
+        .filter_map(solve_crt_system)
+        .map(|(first, period)| {
+            let cycles = (steps as u128).saturating_sub(first).div_ceil(period);
+            first + cycles * period
+        })
+        .min()
+        .map(|steps| {
+            steps
+                .try_into()
+                .expect("the number of steps does not fit in a usize")
+        })
 }
 
 fn parse_instructions<T>(input: &mut T) -> ParseResult<Vec<Direction>>
@@ -169,28 +214,27 @@ where
 
         result.insert(from, Node::new(left, right));
     }
+
+    for node in result.values() {
+        for label in [node.travel(Direction::Left), node.travel(Direction::Right)] {
+            if !result.contains_key(&label) {
+                return err_parse_error!("node {} is not part of the map", label);
+            }
+        }
+    }
+
     Ok(result)
 }
 
 #[cfg(test)]
 mod tests {
-    use super::*;
-
-    fn test_data_1() -> impl Iterator<Item = String> {
-        InputReader::from("./src/day08/test1.txt").read_lines()
-    }
+    use rdcl_aoc_core::include_input;
 
-    fn test_data_2() -> impl Iterator<Item = String> {
-        InputReader::from("./src/day08/test2.txt").read_lines()
-    }
-
-    fn test_data_3() -> impl Iterator<Item = String> {
-        InputReader::from("./src/day08/test3.txt").read_lines()
-    }
+    use super::*;
 
     #[test]
     fn test_follow_1() {
-        let mut input = test_data_1();
+        let mut input = include_input!("test1.txt").read_lines();
         let instructions = parse_instructions(&mut input).unwrap();
         let map = parse_map(input).unwrap();
 
@@ -202,7 +246,7 @@ mod tests {
 
     #[test]
     fn test_follow_2() {
-        let mut input = test_data_2();
+        let mut input = include_input!("test2.txt").read_lines();
         let instructions = parse_instructions(&mut input).unwrap();
         let map = parse_map(input).unwrap();
 
@@ -214,7 +258,7 @@ mod tests {
 
     #[test]
     fn test_follow_3() {
-        let mut input = test_data_3();
+        let mut input = include_input!("test3.txt").read_lines();
         let instructions = parse_instructions(&mut input).unwrap();
         let map = parse_map(input).unwrap();
 
@@ -223,4 +267,87 @@ mod tests {
             Some(6usize)
         );
     }
+
+    #[test]
+    fn test_follow_ghosts_with_offset() {
+        // the first ghost reaches its goal after 3, 5, 7, ... steps, the second after 1, 4, 7, ...
+        let mut input = InputReader::from_text(
+            "L\n\n\
+            11A = (11B, XXX)\n\
+            11B = (11C, XXX)\n\
+            11C = (11Z, XXX)\n\
+            11Z = (11D, XXX)\n\
+            11D = (11Z, XXX)\n\
+            22A = (22Z, XXX)\n\
+            22Z = (22B, XXX)\n\
+            22B = (22C, XXX)\n\
+            22C = (22Z, XXX)\n\
+            XXX = (XXX, XXX)\n",
+        )
+        .read_lines();
+        let instructions = parse_instructions(&mut input).unwrap();
+        let map = parse_map(input).unwrap();
+
+        assert_eq!(
+            follow_ghostly_instructions(&instructions, &map, 'A', 'Z'),
+            Some(7usize)
+        );
+    }
+
+    #[test]
+    fn test_follow_ghosts_with_irregular_arrivals() {
+        // the first ghost reaches its goal after 1, 2, 6, 7, 11, 12, ... steps, so the time
+        // between its first two arrivals is not the period, and the second ghost reaches its goal
+        // after 3, 7, 11, ... steps, which only lines up with the second arrival of each cycle
+        let mut input = InputReader::from_text(
+            "L\n\n\
+            11A = (12Z, XXX)\n\
+            12Z = (11Z, XXX)\n\
+            11Z = (11B, XXX)\n\
+            11B = (11C, XXX)\n\
+            11C = (11A, XXX)\n\
+            22A = (22B, XXX)\n\
+            22B = (22C, XXX)\n\
+            22C = (22Z, XXX)\n\
+            22Z = (22A, XXX)\n\
+            XXX = (XXX, XXX)\n",
+        )
+        .read_lines();
+        let instructions = parse_instructions(&mut input).unwrap();
+        let map = parse_map(input).unwrap();
+
+        assert_eq!(
+            follow_ghostly_instructions(&instructions, &map, 'A', 'Z'),
+            Some(7usize)
+        );
+    }
+
+    #[test]
+    fn test_follow_ghosts_never_aligned() {
+        // the first ghost only reaches its goal after an odd number of steps, the second after an
+        // even number of steps
+        let mut input = InputReader::from_text(
+            "L\n\n\
+            11A = (11Z, XXX)\n\
+            11Z = (11A, XXX)\n\
+            22A = (22B, XXX)\n\
+            22B = (22Z, XXX)\n\
+            22Z = (22B, XXX)\n\
+            XXX = (XXX, XXX)\n",
+        )
+        .read_lines();
+        let instructions = parse_instructions(&mut input).unwrap();
+        let map = parse_map(input).unwrap();
+
+        assert_eq!(
+            follow_ghostly_instructions(&instructions, &map, 'A', 'Z'),
+            None
+        );
+    }
+
+    #[test]
+    fn test_parse_map_with_missing_node() {
+        let input = InputReader::from_text("11A = (11B, 11A)\n").read_lines();
+        assert!(parse_map(input).is_err());
+    }
 }
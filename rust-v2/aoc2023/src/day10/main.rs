@@ -33,15 +33,17 @@ fn main() {
 
 #[cfg(test)]
 mod tests {
+    use rdcl_aoc_core::include_input;
+
     use super::*;
 
-    fn test_data(file: &str) -> PipeMap {
-        InputReader::from(format!("./src/day10/{file}")).parse::<PipeMap>()
+    fn test_data(input: InputReader) -> PipeMap {
+        input.parse::<PipeMap>()
     }
 
     #[test]
     fn test_find_loop_1() {
-        let mut map = test_data("test1.txt");
+        let mut map = test_data(include_input!("test1.txt"));
         // println!("{map}");
         assert_eq!(map.find_loop().unwrap(), 4);
         assert_eq!(map.count_points_inside_loop(), 1);
@@ -49,7 +51,7 @@ mod tests {
 
     #[test]
     fn test_find_loop_2() {
-        let mut map = test_data("test2.txt");
+        let mut map = test_data(include_input!("test2.txt"));
         // println!("{map}");
         assert_eq!(map.find_loop().unwrap(), 8);
         assert_eq!(map.count_points_inside_loop(), 1);
@@ -57,7 +59,7 @@ mod tests {
 
     #[test]
     fn test_find_loop_3() {
-        let mut map = test_data("test3.txt");
+        let mut map = test_data(include_input!("test3.txt"));
         // println!("{map}");
         assert_eq!(map.find_loop().unwrap(), 23);
         assert_eq!(map.count_points_inside_loop(), 4);
@@ -65,7 +67,7 @@ mod tests {
 
     #[test]
     fn test_find_loop_4() {
-        let mut map = test_data("test4.txt");
+        let mut map = test_data(include_input!("test4.txt"));
         // println!("{map}");
         assert_eq!(map.find_loop().unwrap(), 70);
         assert_eq!(map.count_points_inside_loop(), 8);
@@ -73,7 +75,7 @@ mod tests {
 
     #[test]
     fn test_find_loop_5() {
-        let mut map = test_data("test5.txt");
+        let mut map = test_data(include_input!("test5.txt"));
         // println!("{map}");
         assert_eq!(map.find_loop().unwrap(), 80);
         assert_eq!(map.count_points_inside_loop(), 10);
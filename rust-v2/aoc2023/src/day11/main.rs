@@ -32,10 +32,12 @@ fn main() {
 
 #[cfg(test)]
 mod tests {
+    use rdcl_aoc_core::include_input;
+
     use super::*;
 
     fn test_data() -> GalaxyMap {
-        InputReader::from("./src/day11/test.txt").parse()
+        include_input!("test.txt").parse()
     }
 
     #[test]
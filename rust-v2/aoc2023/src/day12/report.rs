@@ -4,6 +4,7 @@ use std::str::FromStr;
 
 use rdcl_aoc_core::err_parse_error;
 use rdcl_aoc_core::error::ParseError;
+use rdcl_aoc_core::parser::parse_separated;
 
 #[derive(Debug, Clone, Default)]
 pub struct Record {
@@ -115,9 +116,7 @@ impl FromStr for Record {
             });
         }
 
-        for s in s[pos + 1..].split(',') {
-            record.groups.push(s.parse()?);
-        }
+        record.groups = parse_separated(&s[pos + 1..], ",")?;
 
         Ok(record)
     }
@@ -20,7 +20,7 @@ struct Args {
 
 fn main() -> MainResult {
     let args: Args = Args::parse();
-    let patterns = InputReader::from(args.input).parse_vec::<Pattern>();
+    let patterns = InputReader::from(args.input).parse_sections::<Vec<Pattern>>();
 
     let mut summary = 0;
     for pattern in &patterns {
@@ -57,12 +57,14 @@ fn main() -> MainResult {
 
 #[cfg(test)]
 mod tests {
+    use rdcl_aoc_core::include_input;
+
     use crate::pattern::Reflection;
 
     use super::*;
 
     fn test_data() -> Vec<Pattern> {
-        InputReader::from("./src/day13/test.txt").parse_vec()
+        include_input!("test.txt").parse_sections()
     }
 
     #[test]
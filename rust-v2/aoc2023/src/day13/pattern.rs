@@ -2,7 +2,7 @@ use std::fmt;
 
 use grid::Grid;
 
-use rdcl_aoc_core::input::VecFromInput;
+use rdcl_aoc_core::input::FromInput;
 use rdcl_aoc_core::{err_parse_error, ParseResult};
 
 #[derive(Debug, Copy, Clone, Default, Eq, PartialEq)]
@@ -156,39 +156,28 @@ impl Pattern {
     }
 }
 
-impl VecFromInput for Pattern {
-    fn parse<T>(input: T) -> ParseResult<Vec<Self>>
+impl FromInput for Pattern {
+    fn parse<T>(input: T) -> ParseResult<Self>
     where
         T: Iterator<Item = String>,
     {
-        let mut result = vec![];
         let mut pattern = Pattern::default();
 
         for line in input {
-            if line.is_empty() {
-                if !pattern.pattern.is_empty() {
-                    result.push(pattern);
-                }
-                pattern = Pattern::default();
-            } else {
-                let mut row = vec![];
-                for ch in line.chars() {
-                    row.push(match ch {
-                        '.' => Field::Ash,
-                        '#' => Field::Rock,
-                        _ => {
-                            return err_parse_error!();
-                        }
-                    });
-                }
-                pattern.pattern.push_row(row);
+            let mut row = vec![];
+            for ch in line.chars() {
+                row.push(match ch {
+                    '.' => Field::Ash,
+                    '#' => Field::Rock,
+                    _ => {
+                        return err_parse_error!();
+                    }
+                });
             }
-        }
-        if !pattern.pattern.is_empty() {
-            result.push(pattern);
+            pattern.pattern.push_row(row);
         }
 
-        Ok(result)
+        Ok(pattern)
     }
 }
 
@@ -1,10 +1,10 @@
 //! The solution for [advent of code 2023, day 14](https://adventofcode.com/2023/day/14)
 
-use std::collections::HashMap;
 use std::path::PathBuf;
 
 use clap::Parser;
 
+use rdcl_aoc_core::cycle::simulate_with_cycle_skip_by_key;
 use rdcl_aoc_core::input::InputReader;
 
 use crate::platform::Platform;
@@ -35,37 +35,18 @@ fn main() {
     );
 }
 
-fn cycle(mut platform: Platform, count: usize) -> Platform {
-    let mut seen: HashMap<String, usize> = HashMap::new();
-
-    let mut i = 0;
-    while i < count {
-        platform = platform.cycle();
-        let key = format!("{platform}");
-
-        if let Some(v) = seen.get(&key) {
-            // loop detected
-            println!(
-                "after {i} iterations, the platform is identical as it was after {v} iterations"
-            );
-            let modulus = i - v;
-            i = count - ((count - v) % modulus);
-            seen.clear();
-        }
-
-        seen.insert(key, i);
-        i += 1;
-    }
-
-    platform
+fn cycle(platform: Platform, count: usize) -> Platform {
+    simulate_with_cycle_skip_by_key(platform, |p| p.cycle(), count, |p| format!("{p}")).state
 }
 
 #[cfg(test)]
 mod tests {
+    use rdcl_aoc_core::include_input;
+
     use super::*;
 
     fn test_data() -> Platform {
-        InputReader::from("./src/day14/test.txt").parse()
+        include_input!("test.txt").parse()
     }
 
     #[test]
@@ -2,6 +2,7 @@ use std::fmt;
 
 use grid::Grid;
 
+use rdcl_aoc_core::grid::{display_grid, parse_grid, GridCell};
 use rdcl_aoc_core::input::FromInput;
 use rdcl_aoc_core::ParseResult;
 
@@ -86,40 +87,15 @@ impl FromInput for Platform {
     where
         T: Iterator<Item = String>,
     {
-        let mut platform = Grid::new(0, 0);
-
-        for line in input {
-            platform.push_row(
-                line.chars()
-                    .map(|ch| match ch {
-                        'O' => Rock::Round,
-                        '#' => Rock::Cube,
-                        _ => Rock::None,
-                    })
-                    .collect(),
-            )
-        }
-
-        Ok(Platform { platform })
+        Ok(Platform {
+            platform: parse_grid(input)?,
+        })
     }
 }
 
 impl fmt::Display for Platform {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        for row in 0..self.platform.rows() {
-            if row != 0 {
-                writeln!(f)?;
-            }
-            for col in 0..self.platform.cols() {
-                match self.platform[(row, col)] {
-                    Rock::None => write!(f, ".")?,
-                    Rock::Cube => write!(f, "#")?,
-                    Rock::Round => write!(f, "O")?,
-                }
-            }
-        }
-
-        Ok(())
+        write!(f, "{}", display_grid(&self.platform))
     }
 }
 
@@ -131,6 +107,25 @@ pub enum Rock {
     Round,
 }
 
+impl GridCell for Rock {
+    fn from_char(ch: char) -> Option<Self> {
+        match ch {
+            'O' => Some(Rock::Round),
+            '#' => Some(Rock::Cube),
+            '.' => Some(Rock::None),
+            _ => None,
+        }
+    }
+
+    fn to_char(&self) -> char {
+        match self {
+            Rock::None => '.',
+            Rock::Cube => '#',
+            Rock::Round => 'O',
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -121,10 +121,12 @@ enum InstructionType {
 
 #[cfg(test)]
 mod tests {
+    use rdcl_aoc_core::include_input;
+
     use super::*;
 
     fn test_data() -> String {
-        InputReader::from("./src/day15/test.txt").read_line()
+        include_input!("test.txt").read_line()
     }
 
     #[test]
@@ -35,10 +35,12 @@ fn main() {
 
 #[cfg(test)]
 mod tests {
+    use rdcl_aoc_core::include_input;
+
     use super::*;
 
     fn test_data() -> Contraption {
-        InputReader::from("./src/day16/test.txt").parse()
+        include_input!("test.txt").parse()
     }
 
     #[test]
@@ -30,15 +30,10 @@ impl City {
             direction: Direction::Right,
             count: 0,
         };
-        let optimal_route = self.find_shortest_path(&start, &self.end)?;
-
-        Some(
-            optimal_route
-                .iter()
-                .skip(1)
-                .map(|step| self.layout[step.point] as u64)
-                .sum(),
-        )
+        let optimal_route =
+            self.find_path_where(&start, &self.end, |step| step.point == self.end, None)?;
+
+        Some(optimal_route.cost)
     }
 
     pub fn upgrade_to_ultra_crucibles(&mut self) {
@@ -148,12 +143,6 @@ impl Step {
     }
 }
 
-impl PartialEq<(usize, usize)> for Step {
-    fn eq(&self, other: &(usize, usize)) -> bool {
-        self.point.eq(other)
-    }
-}
-
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
 pub enum Direction {
     Up,
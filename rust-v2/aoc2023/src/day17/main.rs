@@ -45,10 +45,12 @@ fn main() {
 
 #[cfg(test)]
 mod tests {
+    use rdcl_aoc_core::include_input;
+
     use super::*;
 
     fn test_data() -> City {
-        InputReader::from("./src/day17/test.txt").parse()
+        include_input!("test.txt").parse()
     }
 
     #[test]
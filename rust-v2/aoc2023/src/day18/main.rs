@@ -34,10 +34,12 @@ fn main() {
 
 #[cfg(test)]
 mod tests {
+    use rdcl_aoc_core::include_input;
+
     use super::*;
 
     fn test_data() -> Lagoons {
-        InputReader::from("./src/day18/test.txt").parse()
+        include_input!("test.txt").parse()
     }
 
     #[test]
@@ -4,7 +4,7 @@ use std::path::PathBuf;
 
 use clap::Parser;
 
-use rdcl_aoc_core::input::InputReader;
+use rdcl_aoc_core::input::{InputReader, Sections};
 use rdcl_aoc_core::{MainResult, ParseResult};
 
 use crate::parts::{parse_part, HasRatings, Part};
@@ -30,7 +30,7 @@ struct Args {
 
 fn main() -> MainResult {
     let args: Args = Args::parse();
-    let (workflows, parts) = parse_input(&mut InputReader::from(args.input).read_lines())?;
+    let (workflows, parts) = parse_input(InputReader::from(args.input).read_lines())?;
     let bounds = (args.lower_bound, args.upper_bound);
 
     println!(
@@ -52,18 +52,20 @@ fn main() -> MainResult {
 type ParsedInput = (Workflows, Vec<Part>);
 type ParseInputResult = ParseResult<ParsedInput>;
 
-fn parse_input<T>(input: &mut T) -> ParseInputResult
+fn parse_input<T>(input: T) -> ParseInputResult
 where
     T: Iterator<Item = String>,
 {
+    let mut sections = Sections::from(input);
+
     let mut workflows = Workflows::new();
-    for workflow in input.take_while(|line| !line.is_empty()) {
+    for workflow in sections.next_section()? {
         let workflow = workflow.parse::<Workflow>()?;
         workflows.insert(workflow.name(), workflow);
     }
 
     let mut parts = vec![];
-    for part in input {
+    for part in sections.next_section()? {
         parts.push(parse_part(&part)?);
     }
 
@@ -72,10 +74,12 @@ where
 
 #[cfg(test)]
 mod tests {
+    use rdcl_aoc_core::include_input;
+
     use super::*;
 
     fn test_data() -> ParsedInput {
-        parse_input(&mut InputReader::from("./src/day19/test.txt").read_lines()).unwrap()
+        parse_input(include_input!("test.txt").read_lines()).unwrap()
     }
 
     #[test]
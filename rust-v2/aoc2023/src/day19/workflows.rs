@@ -1,8 +1,9 @@
 use std::collections::{HashMap, VecDeque};
 use std::str::FromStr;
 
-use rdcl_aoc_core::err_parse_error;
 use rdcl_aoc_core::error::ParseError;
+use rdcl_aoc_core::parser::Parser;
+use rdcl_aoc_core::{err_parse_error, ParseResult};
 
 use crate::parts::{Part, Rating, NR_RATINGS};
 
@@ -168,15 +169,12 @@ impl FromStr for Workflow {
     type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let idx = s.find('{').ok_or(())?;
-        let mut workflow = Workflow {
-            name: s[0..idx].to_string(),
-            rules: vec![],
+        let mut parser = Parser::new(s);
+        let workflow = Workflow {
+            name: parser.take_identifier()?.to_string(),
+            rules: parser.delimited("{", |p| p.separated_list(",", Rule::parse), "}")?,
         };
-
-        for rule in s[idx + 1..s.len() - 1].split(',') {
-            workflow.rules.push(rule.parse()?);
-        }
+        parser.expect_end()?;
 
         if workflow.is_valid() {
             Ok(workflow)
@@ -238,34 +236,42 @@ impl Rule {
             _ => bounds,
         }
     }
+
+    /// Parses a rule such as `a<2006:qkq` or `rhg`.
+    fn parse(parser: &mut Parser) -> ParseResult<Rule> {
+        let conditional = |p: &mut Parser| {
+            let rating = p.take_while1("a rating", |ch| ch.is_ascii_lowercase())?;
+            let rating = rating.parse::<Rating>()?;
+            let less = p.alt((
+                |p: &mut Parser| p.skip_text("<").map(|_| true),
+                |p: &mut Parser| p.skip_text(">").map(|_| false),
+            ))?;
+            let value = p.take_unsigned()?;
+            p.skip_text(":")?;
+            let to = p.take_identifier()?.to_string();
+
+            Ok(if less {
+                Rule::Less(rating, value, to)
+            } else {
+                Rule::Greater(rating, value, to)
+            })
+        };
+
+        match parser.optional(conditional) {
+            Some(rule) => Ok(rule),
+            None => Ok(Rule::Always(parser.take_identifier()?.to_string())),
+        }
+    }
 }
 
 impl FromStr for Rule {
     type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        if let Some(idx) = s.find(':') {
-            let rating = s[0..1].parse::<Rating>()?;
-            let operator = s.as_bytes().get(1).copied().ok_or(())?;
-
-            if operator == b'<' {
-                Ok(Rule::Less(
-                    rating,
-                    s[2..idx].parse()?,
-                    s[idx + 1..].to_string(),
-                ))
-            } else if operator == b'>' {
-                Ok(Rule::Greater(
-                    rating,
-                    s[2..idx].parse()?,
-                    s[idx + 1..].to_string(),
-                ))
-            } else {
-                err_parse_error!("Invalid operator: {}", operator)
-            }
-        } else {
-            Ok(Rule::Always(s.to_string()))
-        }
+        let mut parser = Parser::new(s);
+        let rule = Rule::parse(&mut parser)?;
+        parser.expect_end()?;
+        Ok(rule)
     }
 }
 
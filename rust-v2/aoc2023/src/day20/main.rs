@@ -59,16 +59,18 @@ fn do_button_presses(modules: &ModuleMap, count: usize) -> (usize, usize) {
 
 #[cfg(test)]
 mod tests {
+    use rdcl_aoc_core::include_input;
+
     use super::*;
 
     const BUTTON_PUSH_COUNT: usize = 1000;
 
     fn test_data_1() -> ModuleMap {
-        Module::parse_input(InputReader::from("./src/day20/test1.txt").read_lines()).unwrap()
+        Module::parse_input(include_input!("test1.txt").read_lines()).unwrap()
     }
 
     fn test_data_2() -> ModuleMap {
-        Module::parse_input(InputReader::from("./src/day20/test2.txt").read_lines()).unwrap()
+        Module::parse_input(include_input!("test2.txt").read_lines()).unwrap()
     }
 
     #[test]
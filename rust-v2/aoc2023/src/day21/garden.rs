@@ -2,8 +2,9 @@ use std::collections::{HashSet, VecDeque};
 
 use grid::Grid;
 
+use rdcl_aoc_core::grid::{parse_grid_with_markers, GridCell};
 use rdcl_aoc_core::input::FromInput;
-use rdcl_aoc_core::{err_parse_error, ParseResult};
+use rdcl_aoc_core::ParseResult;
 
 #[derive(Debug, Clone)]
 pub struct Garden {
@@ -41,30 +42,12 @@ impl FromInput for Garden {
     where
         T: Iterator<Item = String>,
     {
-        let mut garden = Garden {
-            map: Grid::new(0, 0),
-            starting_position: (0, 0),
-        };
-
-        for (row_idx, line) in input.enumerate() {
-            let mut row = vec![];
-            for (col_idx, ch) in line.chars().enumerate() {
-                row.push(match ch {
-                    'S' => {
-                        garden.starting_position = (row_idx as i64, col_idx as i64);
-                        Plot::GardenPlot
-                    }
-                    '#' => Plot::Rock,
-                    '.' => Plot::GardenPlot,
-                    _ => {
-                        return err_parse_error!("invalid plot encountered: {}", ch);
-                    }
-                });
-            }
-            garden.map.push_row(row);
-        }
+        let (map, [(row, col)]) = parse_grid_with_markers(input, ['S'])?;
 
-        Ok(garden)
+        Ok(Garden {
+            map,
+            starting_position: (row as i64, col as i64),
+        })
     }
 }
 
@@ -75,6 +58,23 @@ enum Plot {
     Rock,
 }
 
+impl GridCell for Plot {
+    fn from_char(ch: char) -> Option<Self> {
+        match ch {
+            '.' | 'S' => Some(Plot::GardenPlot),
+            '#' => Some(Plot::Rock),
+            _ => None,
+        }
+    }
+
+    fn to_char(&self) -> char {
+        match self {
+            Plot::GardenPlot => '.',
+            Plot::Rock => '#',
+        }
+    }
+}
+
 impl Plot {
     fn is_garden_plot(&self) -> bool {
         matches![self, Plot::GardenPlot]
@@ -44,10 +44,12 @@ fn main() {
 
 #[cfg(test)]
 mod tests {
+    use rdcl_aoc_core::include_input;
+
     use super::*;
 
     fn test_data() -> Garden {
-        InputReader::from("./src/day21/test.txt").parse()
+        include_input!("test.txt").parse()
     }
 
     #[test]
@@ -3,8 +3,8 @@ use std::fmt::Formatter;
 use std::str::FromStr;
 
 use rdcl_aoc2023::overlap::Overlappable;
-use rdcl_aoc_core::err_parse_error;
 use rdcl_aoc_core::error::ParseError;
+use rdcl_aoc_core::parse_pattern;
 
 #[derive(Debug, Copy, Clone, Default)]
 pub struct Brick {
@@ -43,21 +43,13 @@ impl FromStr for Brick {
     type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut brick = Brick::default();
-        let mut parts = s.split('~').map(|p| p.parse::<Point>());
-
-        brick.start = parts.next().ok_or(())??;
-        brick.end = parts.next().ok_or(())??;
+        let brick = parse_pattern!(s, "{}~{}" => Brick { start, end })?;
 
         assert!(brick.start.x <= brick.end.x);
         assert!(brick.start.y <= brick.end.y);
         assert!(brick.start.z <= brick.end.z);
 
-        if parts.next().is_some() {
-            err_parse_error!()
-        } else {
-            Ok(brick)
-        }
+        Ok(brick)
     }
 }
 
@@ -78,18 +70,7 @@ impl FromStr for Point {
     type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut point = Point::default();
-        let mut parts = s.split(',').map(|nr| nr.parse::<usize>());
-
-        point.x = parts.next().ok_or(())??;
-        point.y = parts.next().ok_or(())??;
-        point.z = parts.next().ok_or(())??;
-
-        if parts.next().is_some() {
-            err_parse_error!()
-        } else {
-            Ok(point)
-        }
+        parse_pattern!(s, "{},{},{}" => Point { x, y, z })
     }
 }
 
@@ -136,10 +136,12 @@ fn compute_sum_of_falling_bricks_after_disintegrating(
 
 #[cfg(test)]
 mod tests {
+    use rdcl_aoc_core::include_input;
+
     use super::*;
 
     fn test_data() -> Vec<Brick> {
-        InputReader::from("./src/day22/test.txt")
+        include_input!("test.txt")
             .parse_lines(Brick::from_str)
             .collect()
     }
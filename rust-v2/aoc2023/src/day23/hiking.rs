@@ -1,151 +1,11 @@
-use std::collections::{HashMap, HashSet, VecDeque};
-
 use grid::Grid;
 
 use rdcl_aoc_core::input::FromInput;
 use rdcl_aoc_core::ParseResult;
+use rdcl_aoc_pathfinding::graph::compress_corridors;
 
 type Point = (usize, usize);
 
-#[derive(Debug, Copy, Clone)]
-struct EdgeTarget {
-    point: Point,
-    distance: usize,
-}
-
-#[derive(Debug, Copy, Clone)]
-struct ExploringEntry {
-    point: Point,
-    last_junction: Point,
-    came_from: Option<Point>,
-    distance_to_last_junction: usize,
-}
-
-impl ExploringEntry {
-    fn new(point: Point) -> ExploringEntry {
-        ExploringEntry {
-            point,
-            last_junction: point,
-            came_from: None,
-            distance_to_last_junction: 0,
-        }
-    }
-
-    fn next(&self, point: Point) -> ExploringEntry {
-        ExploringEntry {
-            point,
-            last_junction: self.last_junction,
-            came_from: Some(self.point),
-            distance_to_last_junction: self.distance_to_last_junction + 1,
-        }
-    }
-
-    fn apply_to(&self, edges: &mut TrailGraphEdges) {
-        edges
-            .entry(self.last_junction)
-            .or_default()
-            .push(EdgeTarget {
-                point: self.point,
-                distance: self.distance_to_last_junction,
-            });
-    }
-}
-
-type TrailGraphEdges = HashMap<Point, Vec<EdgeTarget>>;
-type Exploring = VecDeque<ExploringEntry>;
-
-#[derive(Debug)]
-struct TrailGraph {
-    edges: HashMap<Point, Vec<EdgeTarget>>,
-    start: Point,
-    end: Point,
-}
-
-impl TrailGraph {
-    /// Convert a trail map to a trail graph, where only the junctions
-    /// are shown together with the possible distances between
-    /// junctions.
-    ///
-    /// It is assumed that the trail map does not contain any clearings,
-    /// i.e. there are no 2x2 blocks of tiles that contain no forests.
-    fn from(trail: &Trail, slopes_are_slippy: bool) -> TrailGraph {
-        // all edges that have been found so far
-        let mut edges = TrailGraphEdges::new();
-
-        // all points that are currently being explored
-        let mut exploring = Exploring::new();
-        exploring.push_back(ExploringEntry::new(trail.start));
-
-        // all junctions that have already been encountered and so do not need exploring again
-        let mut junctions: HashSet<(Point, Point)> = HashSet::new();
-
-        while let Some(entry) = exploring.pop_back() {
-            let mut neighbours = trail.get_neighbours(entry.point, slopes_are_slippy);
-            if let Some(came_from) = entry.came_from {
-                neighbours = neighbours
-                    .iter()
-                    .copied()
-                    .filter(|&n| n != came_from)
-                    .collect();
-            }
-
-            if entry.point == trail.end || neighbours.len() > 1 {
-                // this is a junction
-                if !junctions.contains(&(entry.last_junction, entry.point)) {
-                    junctions.insert((entry.last_junction, entry.point));
-                    entry.apply_to(&mut edges);
-
-                    for neighbour in neighbours {
-                        exploring.push_back(ExploringEntry {
-                            point: neighbour,
-                            last_junction: entry.point,
-                            came_from: Some(entry.point),
-                            distance_to_last_junction: 1,
-                        });
-                    }
-                }
-            } else {
-                // simple passage
-                for neighbour in neighbours {
-                    exploring.push_back(entry.next(neighbour));
-                }
-            }
-        }
-
-        TrailGraph {
-            edges,
-            start: trail.start,
-            end: trail.end,
-        }
-    }
-
-    fn find_longest_hike(&self) -> usize {
-        let mut longest = 0;
-        self.find_recursive(self.start, &mut HashSet::new(), 0, &mut longest);
-        longest
-    }
-
-    fn find_recursive(
-        &self,
-        position: Point,
-        seen: &mut HashSet<Point>,
-        steps: usize,
-        longest: &mut usize,
-    ) {
-        if position == self.end {
-            *longest = (*longest).max(steps);
-        } else if let Some(neighbours) = self.edges.get(&position) {
-            for neighbour in neighbours {
-                if !seen.contains(&neighbour.point) {
-                    seen.insert(neighbour.point);
-                    self.find_recursive(neighbour.point, seen, steps + neighbour.distance, longest);
-                    seen.remove(&neighbour.point);
-                }
-            }
-        }
-    }
-}
-
 #[derive(Debug)]
 pub struct Trail {
     map: Grid<Tile>,
@@ -154,8 +14,15 @@ pub struct Trail {
 }
 
 impl Trail {
+    /// Convert the trail map to a graph of junctions, and find the longest path through it.
     pub fn find_longest_hike(&self, slopes_are_slippy: bool) -> usize {
-        TrailGraph::from(self, slopes_are_slippy).find_longest_hike()
+        let graph = compress_corridors(self.start, [self.end], |&point| {
+            self.get_neighbours(point, slopes_are_slippy)
+        });
+        let start = graph.index_of(&self.start).unwrap();
+        let end = graph.index_of(&self.end).unwrap();
+
+        graph.longest_path(start, end).unwrap_or(0) as usize
     }
 
     /// Return all neighbours for a given point that are:
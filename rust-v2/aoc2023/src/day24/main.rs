@@ -56,10 +56,12 @@ fn count_possible_intersections(hailstones: &[Trajectory], bounds: (i64, i64)) -
 
 #[cfg(test)]
 mod tests {
+    use rdcl_aoc_core::include_input;
+
     use super::*;
 
     fn test_data() -> Vec<Trajectory> {
-        InputReader::from("./src/day24/test.txt")
+        include_input!("test.txt")
             .parse_lines(Trajectory::from_str)
             .collect()
     }
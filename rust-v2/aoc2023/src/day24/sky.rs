@@ -3,7 +3,7 @@ use std::{fmt, mem};
 
 use rdcl_aoc_algebra::algebra3d::Vector3d;
 use rdcl_aoc_core::error::ParseError;
-use rdcl_aoc_core::{err_parse_error, ParseResult};
+use rdcl_aoc_core::parse_pattern;
 
 use crate::fraction::Fraction;
 
@@ -109,29 +109,14 @@ impl FromStr for Trajectory {
     type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut hailstone = Trajectory::default();
         let s = s.replace(' ', "");
-        let mut parts = s.split('@').map(parse_vector);
-        hailstone.position = parts.next().ok_or(())??;
-        hailstone.velocity = parts.next().ok_or(())??;
-        if parts.next().is_none() {
-            Ok(hailstone)
-        } else {
-            err_parse_error!("invalid hail stone: {}", s)
-        }
-    }
-}
+        let (px, py, pz, vx, vy, vz) =
+            parse_pattern!(&s, "{},{},{}@{},{},{}" => i64, i64, i64, i64, i64, i64)?;
 
-fn parse_vector(s: &str) -> ParseResult<Vector3d<i64>> {
-    let mut v = Vector3d::default();
-    let mut parts = s.split(',').map(|p| p.parse::<i64>());
-    v.0 = parts.next().ok_or(())??;
-    v.1 = parts.next().ok_or(())??;
-    v.2 = parts.next().ok_or(())??;
-    if parts.next().is_none() {
-        Ok(v)
-    } else {
-        err_parse_error!("invalid vec3d: {}", s)
+        Ok(Trajectory {
+            position: Vector3d(px, py, pz),
+            velocity: Vector3d(vx, vy, vz),
+        })
     }
 }
 
@@ -38,10 +38,12 @@ fn main() {
 
 #[cfg(test)]
 mod tests {
+    use rdcl_aoc_core::include_input;
+
     use super::*;
 
     fn test_data() -> WiringDiagram {
-        InputReader::from("./src/day25/test.txt").parse()
+        include_input!("test.txt").parse()
     }
 
     #[test]
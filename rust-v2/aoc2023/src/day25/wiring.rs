@@ -1,8 +1,10 @@
-use std::collections::{HashMap, HashSet, VecDeque};
+use std::collections::HashMap;
 use std::fmt;
 
 use rdcl_aoc_core::input::FromInput;
 use rdcl_aoc_core::ParseResult;
+use rdcl_aoc_pathfinding::connectivity::stoer_wagner;
+use rdcl_aoc_pathfinding::graph::Graph;
 
 #[derive(Debug, Clone)]
 pub struct WiringDiagram {
@@ -10,71 +12,21 @@ pub struct WiringDiagram {
 }
 
 impl WiringDiagram {
+    /// Find a way to split the wiring diagram into two groups by cutting at most `cuts` wires.
+    /// Returns the sizes of both groups.
     pub fn find_bisection(&self, cuts: usize) -> Option<(usize, usize)> {
-        if cuts == 0 {
-            self.check_partitioning()
-        } else {
-            let mut clone = self.clone();
-
-            for (from, values) in &self.connections {
-                let it = values.iter().filter(|to| from < to);
-
-                for to in it {
-                    let i = clone.remove_connection(from, to);
-                    let j = clone.remove_connection(to, from);
-                    let result = clone.find_bisection(cuts - 1);
-                    if result.is_some() {
-                        return result;
-                    }
-                    clone.restore_connection(from, to, i);
-                    clone.restore_connection(to, from, j);
-                }
+        let mut graph: Graph<&str, usize> = Graph::new();
+        for (from, values) in &self.connections {
+            for to in values.iter().filter(|to| from < *to) {
+                graph.add_undirected_edge(from, to, 1);
             }
-
-            None
         }
-    }
-
-    fn remove_connection(&mut self, from: &str, to: &str) -> usize {
-        let list = self.connections.get_mut(from).unwrap();
-        let i = list.iter().position(|s| s == to).unwrap();
-        list.remove(i);
-        i
-    }
-
-    fn restore_connection(&mut self, from: &str, to: &str, i: usize) {
-        let list = self.connections.get_mut(from).unwrap();
-        list.insert(i, to.to_string());
-    }
-
-    fn check_partitioning(&self) -> Option<(usize, usize)> {
-        let start = self
-            .connections
-            .keys()
-            .next()
-            .expect("wiring diagram is empty");
-
-        let mut exploring: VecDeque<String> = VecDeque::new();
-        exploring.push_back(start.clone());
-        let mut seen: HashSet<String> = HashSet::new();
-
-        while let Some(component) = exploring.pop_front() {
-            if seen.contains(&component) {
-                continue;
-            }
-            seen.insert(component.clone());
-            for component in self.connections.get(&component).unwrap() {
-                exploring.push_back(component.clone());
-            }
-        }
-
-        let partition_size = seen.len();
-        let nr_components = self.connections.len();
 
-        if partition_size == nr_components {
+        let cut = stoer_wagner(&graph)?;
+        if cut.weight > cuts {
             None
         } else {
-            Some((partition_size, nr_components - partition_size))
+            Some((cut.partition.len(), graph.len() - cut.partition.len()))
         }
     }
 }
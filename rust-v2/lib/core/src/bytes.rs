@@ -0,0 +1,210 @@
+//! Fast, allocation free helpers to scan integers directly from raw bytes.
+//!
+//! These helpers skip the UTF-8 validation and the `FromStr` machinery, which makes them useful
+//! for large inputs that mostly consist of numbers.
+//!
+//! # Usage
+//!
+//! ```
+//! use rdcl_aoc_core::bytes::{signed_ints, take_unsigned, unsigned_ints};
+//!
+//! let line = b"Sensor at x=2, y=-18: closest beacon is at x=-2, y=15";
+//! assert_eq!(signed_ints::<i32>(line).collect::<Vec<_>>(), vec![2, -18, -2, 15]);
+//! assert_eq!(unsigned_ints::<u32>(line).collect::<Vec<_>>(), vec![2, 18, 2, 15]);
+//! assert_eq!(take_unsigned::<u8>(b"42 apples"), Some((42, &b" apples"[..])));
+//! ```
+
+use std::marker::PhantomData;
+
+/// Integer types that can be scanned from ASCII digits.
+pub trait ScanInt: Copy {
+    /// The value zero.
+    const ZERO: Self;
+
+    /// Computes `self * 10 + digit`, or `None` if this overflows.
+    fn push_digit(self, digit: u8) -> Option<Self>;
+
+    /// Computes `self * 10 - digit`, or `None` if this overflows. This is used for negative
+    /// numbers, so that the minimum value of a signed type can be scanned.
+    fn push_negative_digit(self, digit: u8) -> Option<Self>;
+}
+
+macro_rules! impl_scan_int {
+    ($($type:ty),+) => {
+        $(
+            impl ScanInt for $type {
+                const ZERO: Self = 0;
+
+                #[inline]
+                fn push_digit(self, digit: u8) -> Option<Self> {
+                    self.checked_mul(10)?.checked_add(digit as $type)
+                }
+
+                #[inline]
+                fn push_negative_digit(self, digit: u8) -> Option<Self> {
+                    self.checked_mul(10)?.checked_sub(digit as $type)
+                }
+            }
+        )+
+    };
+}
+
+impl_scan_int!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+/// Scans an unsigned integer from the start of `bytes`, and returns it together with the
+/// remaining bytes. Returns `None` if `bytes` does not start with a digit, or if the number does
+/// not fit in `T`.
+pub fn take_unsigned<T: ScanInt>(bytes: &[u8]) -> Option<(T, &[u8])> {
+    take_digits(bytes, T::push_digit)
+}
+
+/// Scans a signed integer (i.e. digits, optionally preceded by `-` or `+`) from the start of
+/// `bytes`, and returns it together with the remaining bytes. Returns `None` if there is no number
+/// at the start of `bytes`, or if the number does not fit in `T`.
+pub fn take_signed<T: ScanInt>(bytes: &[u8]) -> Option<(T, &[u8])> {
+    match bytes.first() {
+        Some(b'-') => take_digits(&bytes[1..], T::push_negative_digit),
+        Some(b'+') => take_digits(&bytes[1..], T::push_digit),
+        _ => take_digits(bytes, T::push_digit),
+    }
+}
+
+/// Parses `bytes` as an unsigned integer. All bytes must be digits.
+pub fn parse_unsigned<T: ScanInt>(bytes: &[u8]) -> Option<T> {
+    match take_unsigned(bytes) {
+        Some((value, [])) => Some(value),
+        _ => None,
+    }
+}
+
+/// Parses `bytes` as a signed integer. All bytes must be digits, except for an optional sign.
+pub fn parse_signed<T: ScanInt>(bytes: &[u8]) -> Option<T> {
+    match take_signed(bytes) {
+        Some((value, [])) => Some(value),
+        _ => None,
+    }
+}
+
+/// Returns an iterator over all unsigned integers in `bytes`, skipping anything that is not a
+/// digit. Iteration stops at the first number that does not fit in `T`.
+pub fn unsigned_ints<T: ScanInt>(bytes: &[u8]) -> Ints<'_, T> {
+    Ints {
+        bytes,
+        signed: false,
+        marker: PhantomData,
+    }
+}
+
+/// Returns an iterator over all signed integers in `bytes`, skipping anything that is not a digit
+/// or a minus sign directly in front of a digit. Iteration stops at the first number that does
+/// not fit in `T`.
+pub fn signed_ints<T: ScanInt>(bytes: &[u8]) -> Ints<'_, T> {
+    Ints {
+        bytes,
+        signed: true,
+        marker: PhantomData,
+    }
+}
+
+/// Iterator over the integers in a byte slice, created by [`unsigned_ints`] or [`signed_ints`].
+#[derive(Debug, Clone)]
+pub struct Ints<'a, T> {
+    bytes: &'a [u8],
+    signed: bool,
+    marker: PhantomData<T>,
+}
+
+impl<T: ScanInt> Iterator for Ints<'_, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        let start = self.bytes.iter().position(|b| b.is_ascii_digit())?;
+        let negative = self.signed && start > 0 && self.bytes[start - 1] == b'-';
+        let digits = &self.bytes[start..];
+        let result = if negative {
+            take_digits(digits, T::push_negative_digit)
+        } else {
+            take_digits(digits, T::push_digit)
+        };
+
+        match result {
+            Some((value, rest)) => {
+                self.bytes = rest;
+                Some(value)
+            }
+            None => {
+                self.bytes = &[];
+                None
+            }
+        }
+    }
+}
+
+fn take_digits<T, F>(bytes: &[u8], push: F) -> Option<(T, &[u8])>
+where
+    T: ScanInt,
+    F: Fn(T, u8) -> Option<T>,
+{
+    let len = bytes.iter().take_while(|b| b.is_ascii_digit()).count();
+    if len == 0 {
+        return None;
+    }
+
+    let mut value = T::ZERO;
+    for &b in &bytes[..len] {
+        value = push(value, b - b'0')?;
+    }
+
+    Some((value, &bytes[len..]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_take_unsigned() {
+        assert_eq!(take_unsigned::<u32>(b"123abc"), Some((123, &b"abc"[..])));
+        assert_eq!(take_unsigned::<u32>(b"abc"), None);
+        assert_eq!(take_unsigned::<u32>(b""), None);
+        assert_eq!(take_unsigned::<u8>(b"256"), None);
+    }
+
+    #[test]
+    fn test_take_signed() {
+        assert_eq!(take_signed::<i32>(b"-12,3"), Some((-12, &b",3"[..])));
+        assert_eq!(take_signed::<i32>(b"+12"), Some((12, &b""[..])));
+        assert_eq!(take_signed::<i8>(b"-128"), Some((-128, &b""[..])));
+        assert_eq!(take_signed::<i8>(b"128"), None);
+        assert_eq!(take_signed::<i32>(b"-"), None);
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            parse_unsigned::<u64>(b"18446744073709551615"),
+            Some(u64::MAX)
+        );
+        assert_eq!(parse_unsigned::<u64>(b"12 "), None);
+        assert_eq!(parse_signed::<i64>(b"-9223372036854775808"), Some(i64::MIN));
+        assert_eq!(parse_signed::<i64>(b"--1"), None);
+    }
+
+    #[test]
+    fn test_unsigned_ints() {
+        let ints = unsigned_ints::<u32>(b"1,-2 foo 345\n6").collect::<Vec<_>>();
+        assert_eq!(ints, vec![1, 2, 345, 6]);
+    }
+
+    #[test]
+    fn test_signed_ints() {
+        let ints = signed_ints::<i32>(b"1,-2 foo- 345\n-6-7").collect::<Vec<_>>();
+        assert_eq!(ints, vec![1, -2, 345, -6, -7]);
+    }
+
+    #[test]
+    fn test_ints_stops_on_overflow() {
+        let ints = unsigned_ints::<u8>(b"1 2 300 4").collect::<Vec<_>>();
+        assert_eq!(ints, vec![1, 2]);
+    }
+}
@@ -0,0 +1,389 @@
+//! Cycle detection for simulations that need to run for a huge number of steps.
+//!
+//! Many puzzles ask for the state of a simulation after something like a billion steps. Such
+//! simulations almost always end up in a cycle, which can be detected and skipped over.
+//!
+//! There are two ways to detect a cycle:
+//! * The hash-keyed variants ([`find_cycle`], [`simulate_with_cycle_skip`], ...) remember every
+//!   state they have seen. They step through the cycle only once, but need memory for every state.
+//! * The variants based on [Brent's algorithm](https://en.wikipedia.org/wiki/Cycle_detection#Brent's_algorithm)
+//!   ([`find_cycle_brent`], ...) only need to keep two states around, at the cost of stepping
+//!   through the cycle a few more times.
+//!
+//! Every variant has a `_by_key` counterpart, which compares a fingerprint of the states rather
+//! than the states themselves. This is useful when some part of the state keeps changing even
+//! though the simulation is periodic (e.g. a counter, or the height of a tower that keeps growing).
+//!
+//! # Usage
+//!
+//! ```
+//! use rdcl_aoc_core::cycle::{find_cycle, simulate_with_cycle_skip};
+//!
+//! let step = |n: u64| (n * n + 1) % 255;
+//!
+//! let cycle = find_cycle(3, step);
+//! assert_eq!((cycle.start, cycle.length), (2, 6));
+//!
+//! // the state after a billion steps is found without simulating a billion steps
+//! let state = simulate_with_cycle_skip(3, step, 1_000_000_000);
+//! assert_eq!(state, simulate_with_cycle_skip(3, step, cycle.reduce(1_000_000_000)));
+//! ```
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// A cycle in a sequence of states. After `start` steps, the sequence repeats every `length` steps.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Cycle {
+    /// The number of steps before the first state that is part of the cycle.
+    pub start: usize,
+    /// The number of steps after which the sequence repeats itself.
+    pub length: usize,
+}
+
+impl Cycle {
+    /// The smallest number of steps that leads to the same state as `n` steps.
+    pub fn reduce(&self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.length
+        }
+    }
+
+    /// The number of complete cycles that are skipped when `n` is reduced (see [`reduce`]).
+    ///
+    /// [`reduce`]: Cycle::reduce
+    pub fn skipped(&self, n: usize) -> usize {
+        (n - self.reduce(n)) / self.length
+    }
+}
+
+/// The result of a simulation in which a cycle may have been skipped.
+#[derive(Debug, Clone)]
+pub struct Skip<S> {
+    /// The final state of the simulation.
+    pub state: S,
+    /// The cycle that was detected, if the simulation ran long enough to find one.
+    pub cycle: Option<Cycle>,
+    /// The number of complete cycles that were skipped.
+    pub skipped: usize,
+}
+
+/// Finds the cycle in the sequence of states that starts with `initial`, by remembering all states.
+/// Never returns if the sequence does not contain a cycle.
+pub fn find_cycle<S, F>(initial: S, step: F) -> Cycle
+where
+    S: Hash + Eq + Clone,
+    F: FnMut(S) -> S,
+{
+    find_cycle_by_key(initial, step, S::clone)
+}
+
+/// Like [`find_cycle`], but compares states by the fingerprint that is computed by `key`.
+pub fn find_cycle_by_key<S, K, F, G>(initial: S, mut step: F, mut key: G) -> Cycle
+where
+    K: Hash + Eq,
+    F: FnMut(S) -> S,
+    G: FnMut(&S) -> K,
+{
+    let mut seen: HashMap<K, usize> = HashMap::new();
+    let mut state = initial;
+    let mut i = 0;
+    loop {
+        if let Some(start) = seen.insert(key(&state), i) {
+            return Cycle {
+                start,
+                length: i - start,
+            };
+        }
+        state = step(state);
+        i += 1;
+    }
+}
+
+/// Finds the cycle in the sequence of states that starts with `initial`, using Brent's algorithm.
+/// Never returns if the sequence does not contain a cycle.
+pub fn find_cycle_brent<S, F>(initial: S, step: F) -> Cycle
+where
+    S: Eq + Clone,
+    F: FnMut(S) -> S,
+{
+    find_cycle_brent_by_key(initial, step, S::clone)
+}
+
+/// Like [`find_cycle_brent`], but compares states by the fingerprint that is computed by `key`.
+pub fn find_cycle_brent_by_key<S, K, F, G>(initial: S, mut step: F, mut key: G) -> Cycle
+where
+    S: Clone,
+    K: Eq,
+    F: FnMut(S) -> S,
+    G: FnMut(&S) -> K,
+{
+    match brent_cycle_length(initial.clone(), &mut step, &mut key, usize::MAX) {
+        Ok(length) => Cycle {
+            start: brent_cycle_start(initial, step, key, length),
+            length,
+        },
+        Err(_) => unreachable!("Brent's algorithm gave up without a limit"),
+    }
+}
+
+/// Finds the length of the cycle, by letting the hare run ahead in ever larger powers of two.
+/// Gives up once the hare has taken `limit` steps, in which case the state of the hare is returned.
+fn brent_cycle_length<S, K, F, G>(
+    initial: S,
+    step: &mut F,
+    key: &mut G,
+    limit: usize,
+) -> Result<usize, S>
+where
+    K: Eq,
+    F: FnMut(S) -> S,
+    G: FnMut(&S) -> K,
+{
+    if limit == 0 {
+        return Err(initial);
+    }
+
+    let mut power = 1;
+    let mut length = 1;
+    let mut steps = 1;
+    let mut tortoise = key(&initial);
+    let mut hare = step(initial);
+    loop {
+        let hare_key = key(&hare);
+        if hare_key == tortoise {
+            return Ok(length);
+        }
+        if steps == limit {
+            return Err(hare);
+        }
+        if power == length {
+            tortoise = hare_key;
+            power *= 2;
+            length = 0;
+        }
+        hare = step(hare);
+        length += 1;
+        steps += 1;
+    }
+}
+
+/// Finds the start of a cycle of known length, by letting the hare start with a head start of one
+/// cycle.
+fn brent_cycle_start<S, K, F, G>(initial: S, mut step: F, mut key: G, length: usize) -> usize
+where
+    S: Clone,
+    K: Eq,
+    F: FnMut(S) -> S,
+    G: FnMut(&S) -> K,
+{
+    let mut tortoise = initial.clone();
+    let mut hare = initial;
+    for _ in 0..length {
+        hare = step(hare);
+    }
+    let mut start = 0;
+    while key(&tortoise) != key(&hare) {
+        tortoise = step(tortoise);
+        hare = step(hare);
+        start += 1;
+    }
+
+    start
+}
+
+/// Applies `step` to `initial` `n` times. As soon as a state is seen for the second time, all
+/// remaining complete cycles are skipped.
+pub fn simulate_with_cycle_skip<S, F>(initial: S, step: F, n: usize) -> S
+where
+    S: Hash + Eq + Clone,
+    F: FnMut(S) -> S,
+{
+    simulate_with_cycle_skip_by_key(initial, step, n, S::clone).state
+}
+
+/// Like [`simulate_with_cycle_skip`], but compares states by the fingerprint that is computed by
+/// `key`. Since the final state is only equal to the actual state after `n` steps as far as the
+/// fingerprint is concerned, the result also tells how many cycles were skipped. This can be used
+/// to extrapolate any part of the state that is not covered by the fingerprint.
+pub fn simulate_with_cycle_skip_by_key<S, K, F, G>(
+    initial: S,
+    mut step: F,
+    n: usize,
+    mut key: G,
+) -> Skip<S>
+where
+    K: Hash + Eq,
+    F: FnMut(S) -> S,
+    G: FnMut(&S) -> K,
+{
+    let mut seen: HashMap<K, usize> = HashMap::new();
+    let mut state = initial;
+    let mut i = 0;
+    while i < n {
+        if let Some(start) = seen.insert(key(&state), i) {
+            let cycle = Cycle {
+                start,
+                length: i - start,
+            };
+            for _ in 0..(n - i) % cycle.length {
+                state = step(state);
+            }
+            return Skip {
+                state,
+                cycle: Some(cycle),
+                skipped: (n - i) / cycle.length,
+            };
+        }
+        state = step(state);
+        i += 1;
+    }
+
+    Skip {
+        state,
+        cycle: None,
+        skipped: 0,
+    }
+}
+
+/// Like [`simulate_with_cycle_skip`], but uses Brent's algorithm to find the cycle, so the states
+/// do not have to be remembered.
+pub fn simulate_with_cycle_skip_brent<S, F>(initial: S, step: F, n: usize) -> S
+where
+    S: Eq + Clone,
+    F: FnMut(S) -> S,
+{
+    simulate_with_cycle_skip_brent_by_key(initial, step, n, S::clone).state
+}
+
+/// Like [`simulate_with_cycle_skip_by_key`], but uses Brent's algorithm to find the cycle, so the
+/// states do not have to be remembered. The cycle is found first, after which the simulation is
+/// restarted for the reduced number of steps. If no cycle has been found after `n` steps, the
+/// simulation simply stops there.
+pub fn simulate_with_cycle_skip_brent_by_key<S, K, F, G>(
+    initial: S,
+    mut step: F,
+    n: usize,
+    mut key: G,
+) -> Skip<S>
+where
+    S: Clone,
+    K: Eq,
+    F: FnMut(S) -> S,
+    G: FnMut(&S) -> K,
+{
+    let length = match brent_cycle_length(initial.clone(), &mut step, &mut key, n) {
+        Ok(length) => length,
+        Err(state) => {
+            return Skip {
+                state,
+                cycle: None,
+                skipped: 0,
+            }
+        }
+    };
+    let cycle = Cycle {
+        start: brent_cycle_start(initial.clone(), &mut step, key, length),
+        length,
+    };
+    let mut state = initial;
+    for _ in 0..cycle.reduce(n) {
+        state = step(state);
+    }
+
+    Skip {
+        state,
+        cycle: Some(cycle),
+        skipped: cycle.skipped(n),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn step(n: u64) -> u64 {
+        (n * n + 1) % 255
+    }
+
+    fn naive(mut state: u64, n: usize) -> u64 {
+        for _ in 0..n {
+            state = step(state);
+        }
+        state
+    }
+
+    #[test]
+    fn test_find_cycle() {
+        for initial in 0..255 {
+            assert_eq!(find_cycle(initial, step), find_cycle_brent(initial, step));
+        }
+        assert_eq!(
+            find_cycle(3, step),
+            Cycle {
+                start: 2,
+                length: 6
+            }
+        );
+    }
+
+    #[test]
+    fn test_cycle_reduce() {
+        let cycle = Cycle {
+            start: 3,
+            length: 4,
+        };
+        assert_eq!(cycle.reduce(2), 2);
+        assert_eq!(cycle.reduce(3), 3);
+        assert_eq!(cycle.reduce(7), 3);
+        assert_eq!(cycle.reduce(12), 4);
+        assert_eq!(cycle.skipped(12), 2);
+        assert_eq!(cycle.skipped(2), 0);
+    }
+
+    #[test]
+    fn test_simulate_with_cycle_skip() {
+        for n in 0..50 {
+            assert_eq!(simulate_with_cycle_skip(3, step, n), naive(3, n));
+            assert_eq!(simulate_with_cycle_skip_brent(3, step, n), naive(3, n));
+        }
+    }
+
+    #[test]
+    fn test_simulate_with_cycle_skip_brent_without_cycle() {
+        // the sequence never repeats, so the simulation must stop after exactly n steps
+        let mut steps = 0;
+        let skip = simulate_with_cycle_skip_brent_by_key(
+            0u64,
+            |n| {
+                steps += 1;
+                n + 1
+            },
+            100,
+            |&n| n,
+        );
+        assert_eq!(skip.state, 100);
+        assert_eq!(skip.cycle, None);
+        assert_eq!(skip.skipped, 0);
+        assert_eq!(steps, 100);
+    }
+
+    #[test]
+    fn test_simulate_with_cycle_skip_by_key() {
+        // the counter keeps growing, but the fingerprint ignores it
+        let step = |(n, count): (u64, usize)| (step(n), count + 1);
+
+        for n in 0..50 {
+            let skip = simulate_with_cycle_skip_by_key((3, 0), step, n, |&(n, _)| n);
+            let cycle_length = skip.cycle.map_or(0, |cycle| cycle.length);
+            assert_eq!(skip.state.0, naive(3, n));
+            assert_eq!(skip.state.1 + skip.skipped * cycle_length, n);
+
+            let skip = simulate_with_cycle_skip_brent_by_key((3, 0), step, n, |&(n, _)| n);
+            assert_eq!(skip.state.0, naive(3, n));
+            assert_eq!(skip.state.1 + skip.skipped * 6, n);
+        }
+    }
+}
@@ -1,48 +1,366 @@
 //! Error handling.
 
+use std::error::Error;
 use std::fmt;
+use std::ops::Range;
+use std::sync::Arc;
 
 /// Generic parsing error.
+///
+/// A parse error consists of an [`ErrorKind`], which describes what went wrong. Besides that, a
+/// parse error can optionally keep track of where in the input the error occurred (the name of
+/// the input, the line number and the span within that line), and of the underlying error that
+/// caused it. If location information is available, the alternate format (`{:#}`) renders a
+/// diagnostic in the style of rustc:
+///
+/// ```text
+/// error: Expected 'y=', found 'z=2'
+///  --> input.txt:3:6
+///   |
+/// 3 | x=1, z=2
+///   |      ^^
+/// ```
+#[derive(Debug, Clone)]
+pub struct ParseError {
+    kind: ErrorKind,
+    location: Box<Location>,
+    source: Option<Arc<dyn Error + Send + Sync>>,
+}
+
+/// The different kinds of parse errors. This allows callers (and tests) to inspect what went
+/// wrong, without depending on the exact wording of the message.
 #[derive(Debug, Clone, Eq, PartialEq)]
-pub struct ParseError(pub String);
+pub enum ErrorKind {
+    /// The input ended, while `expected` was still expected.
+    UnexpectedEnd { expected: String },
+    /// The input contains `found`, where `expected` was expected.
+    UnexpectedText { expected: String, found: String },
+    /// A number could not be parsed. The text is empty if it is not known (e.g. when the error
+    /// was converted from a [`ParseIntError`](std::num::ParseIntError)).
+    InvalidNumber { text: String, reason: String },
+    /// A value could not be parsed. The text is empty if it is not known.
+    InvalidValue { text: String, reason: String },
+    /// The element at position `index` (starting at 0) of a separated list could not be parsed.
+    InvalidElement {
+        index: usize,
+        text: String,
+        reason: String,
+    },
+    /// A character in a grid does not represent a valid cell. The position is given as
+    /// `(row, col)`.
+    InvalidCell { ch: char, pos: (usize, usize) },
+    /// A marker occurs more than once in a grid. The position of the second occurrence is given
+    /// as `(row, col)`.
+    DuplicateMarker { marker: char, pos: (usize, usize) },
+    /// A marker does not occur in a grid.
+    MissingMarker { marker: char },
+    /// A row in a grid does not have the same number of cells as the rows before it.
+    RowLength { expected: usize, found: usize },
+    /// The input could not be read.
+    Io(String),
+    /// The input is well-formed, but does not make sense.
+    Semantic(String),
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ErrorKind::UnexpectedEnd { expected } => {
+                write!(f, "Unexpected end of input, expected {}.", expected)
+            }
+            ErrorKind::UnexpectedText { expected, found } => {
+                write!(f, "Expected {}, found {}", expected, found)
+            }
+            ErrorKind::InvalidNumber { text, reason } if !text.is_empty() => {
+                write!(
+                    f,
+                    "Could not parse number '{}' with error '{}'",
+                    text, reason
+                )
+            }
+            ErrorKind::InvalidValue { text, reason } if !text.is_empty() => {
+                write!(
+                    f,
+                    "Could not parse value '{}' with error '{}'",
+                    text, reason
+                )
+            }
+            ErrorKind::InvalidNumber { reason, .. } | ErrorKind::InvalidValue { reason, .. } => {
+                write!(f, "{}", reason)
+            }
+            ErrorKind::InvalidElement {
+                index,
+                text,
+                reason,
+            } => write!(
+                f,
+                "Could not parse element {} ('{}') with error '{}'",
+                index, text, reason
+            ),
+            ErrorKind::InvalidCell { ch, .. } => write!(f, "Invalid cell '{}'", ch),
+            ErrorKind::DuplicateMarker { marker, .. } => {
+                write!(f, "Duplicate marker '{}'", marker)
+            }
+            ErrorKind::MissingMarker { marker } => {
+                write!(f, "Marker '{}' does not occur in the grid", marker)
+            }
+            ErrorKind::RowLength { expected, found } => write!(
+                f,
+                "Expected a row of {} cells, but found {} cells",
+                expected, found
+            ),
+            ErrorKind::Io(message) | ErrorKind::Semantic(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+/// Where in the input a parse error occurred. This is kept behind a pointer, so that results
+/// containing a parse error remain small.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+struct Location {
+    input_name: Option<String>,
+    line_nr: Option<usize>,
+    line: Option<String>,
+    span: Option<Range<usize>>,
+}
+
+impl ParseError {
+    /// Construct a new [`ErrorKind::Semantic`] parse error without any location information.
+    pub fn new(message: impl Into<String>) -> ParseError {
+        ParseError::from(ErrorKind::Semantic(message.into()))
+    }
+
+    /// What went wrong.
+    pub fn kind(&self) -> &ErrorKind {
+        &self.kind
+    }
+
+    /// The message describing what went wrong.
+    pub fn message(&self) -> String {
+        self.kind.to_string()
+    }
+
+    /// The name of the input (usually a file name) in which the error occurred.
+    pub fn input_name(&self) -> Option<&str> {
+        self.location.input_name.as_deref()
+    }
+
+    /// The line number (starting at 1) on which the error occurred.
+    pub fn line_nr(&self) -> Option<usize> {
+        self.location.line_nr
+    }
+
+    /// The text of the line on which the error occurred.
+    pub fn line(&self) -> Option<&str> {
+        self.location.line.as_deref()
+    }
+
+    /// The byte range within the line that caused the error.
+    pub fn span(&self) -> Option<Range<usize>> {
+        self.location.span.clone()
+    }
+
+    /// The column (starting at 1, counted in characters) at which the error occurred.
+    pub fn column(&self) -> Option<usize> {
+        match (&self.location.line, &self.location.span) {
+            (Some(line), Some(span)) => Some(line.get(..span.start)?.chars().count() + 1),
+            _ => None,
+        }
+    }
+
+    /// Sets the name of the input, unless it was already set.
+    pub fn with_input_name(mut self, name: impl Into<String>) -> ParseError {
+        if self.location.input_name.is_none() {
+            self.location.input_name = Some(name.into());
+        }
+        self
+    }
+
+    /// Sets the line number and the text of the line on which the error occurred, unless the line
+    /// number was already set.
+    ///
+    /// If the error refers to some other text (e.g. because a parser was only given part of the
+    /// line), the span is dropped, as it does not point into `line`. Use [`within`] first to keep
+    /// it.
+    ///
+    /// [`within`]: ParseError::within
+    pub fn with_line(mut self, line_nr: usize, line: &str) -> ParseError {
+        if self.location.line_nr.is_some() {
+            return self;
+        }
+        self.location.line_nr = Some(line_nr);
+        if self.location.line.as_deref() != Some(line) {
+            self.location.span = None;
+        }
+        self.location.line = Some(line.to_string());
+        self
+    }
+
+    /// Moves the error into a larger piece of text, in which the text the error refers to starts
+    /// at byte `offset`. The span is shifted so it points into `line`.
+    pub fn within(mut self, line: &str, offset: usize) -> ParseError {
+        if let Some(span) = &self.location.span {
+            self.location.span = Some(span.start + offset..span.end + offset);
+        }
+        self.location.line = Some(line.to_string());
+        self
+    }
+
+    /// Sets the text in which the error occurred, together with the byte range that caused it.
+    pub fn with_span(mut self, line: &str, span: Range<usize>) -> ParseError {
+        self.location.line = Some(line.to_string());
+        self.location.span = Some(span);
+        self
+    }
+
+    /// Sets the underlying error that caused this error.
+    pub fn with_source<E: Error + Send + Sync + 'static>(mut self, source: E) -> ParseError {
+        self.source = Some(Arc::new(source));
+        self
+    }
+
+    /// Renders the error as a diagnostic which shows the offending line, with a caret underneath
+    /// the span that caused the error.
+    pub fn diagnostic(&self) -> String {
+        format!("{:#}", self)
+    }
+
+    /// The location of the error, formatted as `name:line:column`.
+    fn format_location(&self) -> Option<String> {
+        if self.location.input_name.is_none() && self.location.line_nr.is_none() {
+            return None;
+        }
+
+        let parts = [
+            self.location.input_name.clone(),
+            self.location.line_nr.map(|nr| nr.to_string()),
+            self.column().map(|col| col.to_string()),
+        ]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>();
+
+        Some(parts.join(":"))
+    }
+
+    fn fmt_diagnostic(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "error: {}", self.kind)?;
+
+        let gutter = self
+            .location
+            .line_nr
+            .map(|nr| nr.to_string())
+            .unwrap_or_default();
+        let padding = " ".repeat(gutter.len());
+        if let Some(location) = self.format_location() {
+            write!(f, "\n{}--> {}", padding, location)?;
+        }
+
+        if let (Some(line), Some(span)) = (&self.location.line, &self.location.span) {
+            let before = line.get(..span.start).unwrap_or(line);
+            let marked = line.get(span.clone()).unwrap_or_default();
+            write!(f, "\n{} |", padding)?;
+            write!(f, "\n{} | {}", gutter, line)?;
+            write!(
+                f,
+                "\n{} | {}{}",
+                padding,
+                " ".repeat(before.chars().count()),
+                "^".repeat(marked.chars().count().max(1))
+            )?;
+        }
+
+        // errors converted from the standard library already use the message of their source
+        if let Some(source) = self.source.as_ref().map(|e| e.to_string()) {
+            if source != self.kind.to_string() {
+                write!(f, "\n{} = caused by: {}", padding, source)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl PartialEq for ParseError {
+    fn eq(&self, other: &Self) -> bool {
+        self.kind == other.kind
+            && self.location == other.location
+            && self.source.as_ref().map(|e| e.to_string())
+                == other.source.as_ref().map(|e| e.to_string())
+    }
+}
+
+impl Eq for ParseError {}
 
 impl fmt::Display for ParseError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", self.0)
+        if f.alternate() {
+            self.fmt_diagnostic(f)
+        } else if let Some(location) = self.format_location() {
+            write!(f, "{}: {}", location, self.kind)
+        } else {
+            write!(f, "{}", self.kind)
+        }
+    }
+}
+
+impl From<ErrorKind> for ParseError {
+    fn from(kind: ErrorKind) -> Self {
+        ParseError {
+            kind,
+            location: Box::default(),
+            source: None,
+        }
     }
 }
 
 impl From<()> for ParseError {
     fn from(_value: ()) -> Self {
-        ParseError("could not parse".to_string())
+        ParseError::new("could not parse")
     }
 }
 
 impl From<std::io::Error> for ParseError {
     fn from(err: std::io::Error) -> Self {
-        ParseError(format!("{:?}", err))
+        ParseError::from(ErrorKind::Io(err.to_string())).with_source(err)
     }
 }
 
 impl From<std::num::ParseIntError> for ParseError {
     fn from(err: std::num::ParseIntError) -> Self {
-        ParseError(format!("{:?}", err))
+        let kind = ErrorKind::InvalidNumber {
+            text: String::new(),
+            reason: err.to_string(),
+        };
+        ParseError::from(kind).with_source(err)
     }
 }
 
 impl From<std::num::ParseFloatError> for ParseError {
     fn from(err: std::num::ParseFloatError) -> Self {
-        ParseError(format!("{:?}", err))
+        let kind = ErrorKind::InvalidNumber {
+            text: String::new(),
+            reason: err.to_string(),
+        };
+        ParseError::from(kind).with_source(err)
     }
 }
 
 impl From<std::char::ParseCharError> for ParseError {
     fn from(err: std::char::ParseCharError) -> Self {
-        ParseError(format!("{:?}", err))
+        let kind = ErrorKind::InvalidValue {
+            text: String::new(),
+            reason: err.to_string(),
+        };
+        ParseError::from(kind).with_source(err)
     }
 }
 
-impl std::error::Error for ParseError {}
+impl Error for ParseError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.source.as_deref().map(|e| e as &(dyn Error + 'static))
+    }
+}
 
 /// Macro to produce a ParseError, with optional interpolation (using format!).
 #[macro_export]
@@ -51,10 +369,10 @@ macro_rules! parse_error {
         $crate::error::ParseError::from(())
     }};
     ($err:expr) => {{
-        $crate::error::ParseError($err.to_string())
+        $crate::error::ParseError::new($err.to_string())
     }};
     ($err:expr, $($args:tt)*) => {{
-        $crate::error::ParseError(format!($err, $($args)*))
+        $crate::error::ParseError::new(format!($err, $($args)*))
     }};
 }
 
@@ -106,16 +424,13 @@ mod tests {
     #[test]
     fn test_macro_parse_error_simple() {
         let err = parse_error!["This is a simple error."];
-        assert_eq!(err, ParseError("This is a simple error.".to_string()));
+        assert_eq!(err, ParseError::new("This is a simple error."));
     }
 
     #[test]
     fn test_macro_parse_error_with_formatting() {
         let err = parse_error!["This is an error with {}.", "formatting"];
-        assert_eq!(
-            err,
-            ParseError("This is an error with formatting.".to_string())
-        );
+        assert_eq!(err, ParseError::new("This is an error with formatting."));
     }
 
     #[test]
@@ -127,7 +442,7 @@ mod tests {
     #[test]
     fn test_macro_err_parse_error_simple() {
         let err: ParseResult<()> = err_parse_error!["This is a simple error."];
-        assert_eq!(err, Err(ParseError("This is a simple error.".to_string())));
+        assert_eq!(err, Err(ParseError::new("This is a simple error.")));
     }
 
     #[test]
@@ -136,7 +451,7 @@ mod tests {
 
         assert_eq!(
             err,
-            Err(ParseError("This is an error with formatting.".to_string()))
+            Err(ParseError::new("This is an error with formatting."))
         );
     }
 
@@ -177,7 +492,7 @@ mod tests {
             Ok(())
         }
 
-        assert_eq!(f(), Err(ParseError("This is a simple error.".to_string())));
+        assert_eq!(f(), Err(ParseError::new("This is a simple error.")));
     }
 
     #[test]
@@ -199,7 +514,109 @@ mod tests {
 
         assert_eq!(
             f(),
-            Err(ParseError("This is an error with formatting.".to_string()))
+            Err(ParseError::new("This is an error with formatting."))
+        );
+    }
+
+    #[test]
+    fn test_display_without_location() {
+        let err = ParseError::new("Something went wrong.");
+
+        assert_eq!(err.to_string(), "Something went wrong.");
+        assert_eq!(err.diagnostic(), "error: Something went wrong.");
+    }
+
+    #[test]
+    fn test_display_with_location() {
+        let err = ParseError::new("Invalid value.")
+            .with_span("x=1, y=abc", 7..10)
+            .with_line(3, "x=1, y=abc")
+            .with_input_name("input.txt");
+
+        assert_eq!(err.column(), Some(8));
+        assert_eq!(err.to_string(), "input.txt:3:8: Invalid value.");
+        assert_eq!(
+            err.diagnostic(),
+            "\
+error: Invalid value.
+ --> input.txt:3:8
+  |
+3 | x=1, y=abc
+  |        ^^^"
+        );
+    }
+
+    #[test]
+    fn test_within_shifts_span() {
+        // the same text occurs twice in the line, so the offset cannot be guessed
+        let err = ParseError::new("Invalid value.")
+            .with_span("abc", 0..3)
+            .within("abc abc", 4)
+            .with_line(1, "abc abc");
+
+        assert_eq!(err.span(), Some(4..7));
+        assert_eq!(err.line(), Some("abc abc"));
+        assert_eq!(err.column(), Some(5));
+    }
+
+    #[test]
+    fn test_with_line_drops_foreign_span() {
+        let err = ParseError::new("Invalid value.")
+            .with_span("y=abc", 2..5)
+            .with_line(1, "x=1, y=abc");
+
+        assert_eq!(err.span(), None);
+        assert_eq!(err.line(), Some("x=1, y=abc"));
+    }
+
+    #[test]
+    fn test_error_kinds() {
+        assert_eq!(
+            parse_error!("Not a {}.", "pipe").kind(),
+            &ErrorKind::Semantic("Not a pipe.".to_string())
+        );
+        assert!(matches!(
+            ParseError::from("-1".parse::<u8>().unwrap_err()).kind(),
+            ErrorKind::InvalidNumber { .. }
+        ));
+        assert!(matches!(
+            ParseError::from("ab".parse::<char>().unwrap_err()).kind(),
+            ErrorKind::InvalidValue { .. }
+        ));
+
+        let err = ParseError::from(ErrorKind::UnexpectedText {
+            expected: "'y='".to_string(),
+            found: "'z=2'".to_string(),
+        });
+        assert_eq!(err.to_string(), "Expected 'y=', found 'z=2'");
+    }
+
+    #[test]
+    fn test_source_is_preserved() {
+        let err = ParseError::from("abc".parse::<u8>().unwrap_err());
+
+        assert_eq!(err.message(), "invalid digit found in string");
+        assert!(err.source().is_some());
+        assert_eq!(
+            err.with_span("abc", 0..3).diagnostic(),
+            "\
+error: invalid digit found in string
+ |
+ | abc
+ | ^^^"
+        );
+    }
+
+    #[test]
+    fn test_source_with_different_message() {
+        let source = "abc".parse::<u8>().unwrap_err();
+        let err = ParseError::new("Invalid weight.").with_source(source);
+
+        assert_eq!(
+            err.diagnostic(),
+            "\
+error: Invalid weight.
+ = caused by: invalid digit found in string"
         );
     }
 }
@@ -0,0 +1,312 @@
+//! Helpers to parse puzzle inputs that consist of a two-dimensional grid of characters.
+//!
+//! Every character in the input is mapped to a cell using the [`GridCell`] trait. The same trait
+//! is used to render a grid again, so a grid that is parsed and then displayed results in the
+//! original input.
+//!
+//! # Usage
+//!
+//! ```
+//! use grid::Grid;
+//! use rdcl_aoc_core::grid::{display_grid, GridCell};
+//! use rdcl_aoc_core::input::InputReader;
+//!
+//! #[derive(Debug, Copy, Clone, PartialEq)]
+//! enum Tile {
+//!     Open,
+//!     Wall,
+//! }
+//!
+//! impl GridCell for Tile {
+//!     fn from_char(ch: char) -> Option<Self> {
+//!         match ch {
+//!             '.' | 'S' | 'E' => Some(Tile::Open),
+//!             '#' => Some(Tile::Wall),
+//!             _ => None,
+//!         }
+//!     }
+//!
+//!     fn to_char(&self) -> char {
+//!         match self {
+//!             Tile::Open => '.',
+//!             Tile::Wall => '#',
+//!         }
+//!     }
+//! }
+//!
+//! let input = InputReader::from_text("S.#\n#.E\n");
+//! let (grid, [start, end]) = input.parse_grid_with_markers::<Tile, 2>(['S', 'E']);
+//!
+//! assert_eq!(start, (0, 0));
+//! assert_eq!(end, (1, 2));
+//! assert_eq!(grid[(0, 2)], Tile::Wall);
+//! assert_eq!(display_grid(&grid).to_string(), "..#\n#..");
+//! ```
+
+use std::fmt;
+
+use grid::Grid;
+
+use crate::error::{ErrorKind, ParseError};
+use crate::input::FromInput;
+use crate::ParseResult;
+
+/// A parsed grid, together with the positions (as `(row, col)`) of the requested markers.
+pub type GridWithMarkers<T, const N: usize> = (Grid<T>, [(usize, usize); N]);
+
+/// A cell in a grid, which is represented by a single character in the input.
+pub trait GridCell: Sized {
+    /// Converts a character into a cell. Returns `None` if the character is not a valid cell.
+    fn from_char(ch: char) -> Option<Self>;
+
+    /// Converts a cell back into the character that represents it.
+    fn to_char(&self) -> char;
+}
+
+impl GridCell for char {
+    fn from_char(ch: char) -> Option<Self> {
+        Some(ch)
+    }
+
+    fn to_char(&self) -> char {
+        *self
+    }
+}
+
+impl<T: GridCell> FromInput for Grid<T> {
+    fn parse<I>(input: I) -> ParseResult<Self>
+    where
+        I: Iterator<Item = String>,
+    {
+        parse_grid(input)
+    }
+}
+
+/// Parses lines of characters into a grid. All lines must have the same length.
+pub fn parse_grid<T, I>(lines: I) -> ParseResult<Grid<T>>
+where
+    T: GridCell,
+    I: Iterator<Item = String>,
+{
+    parse_grid_with_markers(lines, []).map(|(grid, _)| grid)
+}
+
+/// Parses lines of characters into a grid, and returns the positions (as `(row, col)`) of the
+/// specified markers. Every marker must occur exactly once in the input. The characters that are
+/// used as markers are still converted into cells, so `T` must accept them.
+pub fn parse_grid_with_markers<T, I, const N: usize>(
+    lines: I,
+    markers: [char; N],
+) -> ParseResult<GridWithMarkers<T, N>>
+where
+    T: GridCell,
+    I: Iterator<Item = String>,
+{
+    let mut cells = vec![];
+    let mut cols = None;
+    let mut positions: [Option<(usize, usize)>; N] = [None; N];
+
+    for (row, line) in lines.enumerate() {
+        let mut count = 0;
+        for (col, (idx, ch)) in line.char_indices().enumerate() {
+            let span = idx..idx + ch.len_utf8();
+            let cell = T::from_char(ch).ok_or_else(|| {
+                ParseError::from(ErrorKind::InvalidCell {
+                    ch,
+                    pos: (row, col),
+                })
+                .with_span(&line, span.clone())
+                .with_line(row + 1, &line)
+            })?;
+            if let Some(i) = markers.iter().position(|&marker| marker == ch) {
+                if positions[i].is_some() {
+                    let kind = ErrorKind::DuplicateMarker {
+                        marker: ch,
+                        pos: (row, col),
+                    };
+                    return Err(ParseError::from(kind)
+                        .with_span(&line, span)
+                        .with_line(row + 1, &line));
+                }
+                positions[i] = Some((row, col));
+            }
+            cells.push(cell);
+            count += 1;
+        }
+
+        match cols {
+            None => cols = Some(count),
+            Some(expected) if expected != count => {
+                let kind = ErrorKind::RowLength {
+                    expected,
+                    found: count,
+                };
+                return Err(ParseError::from(kind).with_line(row + 1, &line));
+            }
+            _ => {}
+        }
+    }
+
+    let mut found = [(0, 0); N];
+    for (i, position) in positions.iter().enumerate() {
+        match position {
+            Some(position) => found[i] = *position,
+            None => {
+                return Err(ParseError::from(ErrorKind::MissingMarker {
+                    marker: markers[i],
+                }))
+            }
+        }
+    }
+
+    Ok((Grid::from_vec(cells, cols.unwrap_or(0)), found))
+}
+
+/// Renders a grid using the characters that represent its cells. Rows are separated by newlines,
+/// and there is no trailing newline.
+pub fn display_grid<T: GridCell>(grid: &Grid<T>) -> DisplayGrid<'_, T> {
+    DisplayGrid(grid)
+}
+
+/// Helper struct for rendering a grid, created by [`display_grid`].
+pub struct DisplayGrid<'a, T>(&'a Grid<T>);
+
+impl<T: GridCell> fmt::Display for DisplayGrid<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in 0..self.0.rows() {
+            if row != 0 {
+                writeln!(f)?;
+            }
+            for col in 0..self.0.cols() {
+                write!(f, "{}", self.0[(row, col)].to_char())?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Copy, Clone, PartialEq)]
+    enum Cell {
+        Empty,
+        Rock,
+    }
+
+    impl GridCell for Cell {
+        fn from_char(ch: char) -> Option<Self> {
+            match ch {
+                '.' | 'S' => Some(Cell::Empty),
+                '#' => Some(Cell::Rock),
+                _ => None,
+            }
+        }
+
+        fn to_char(&self) -> char {
+            match self {
+                Cell::Empty => '.',
+                Cell::Rock => '#',
+            }
+        }
+    }
+
+    fn lines(lines: &[&str]) -> impl Iterator<Item = String> {
+        lines
+            .iter()
+            .map(|line| line.to_string())
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    #[test]
+    fn test_parse_grid() {
+        let grid = parse_grid::<Cell, _>(lines(&["..#", "#.."])).unwrap();
+
+        assert_eq!(grid.size(), (2, 3));
+        assert_eq!(grid[(0, 2)], Cell::Rock);
+        assert_eq!(grid[(1, 0)], Cell::Rock);
+        assert_eq!(grid[(1, 1)], Cell::Empty);
+    }
+
+    #[test]
+    fn test_parse_empty_grid() {
+        let grid = parse_grid::<Cell, _>(lines(&[])).unwrap();
+
+        assert!(grid.is_empty());
+    }
+
+    #[test]
+    fn test_parse_grid_with_markers() {
+        let (grid, [start]) =
+            parse_grid_with_markers::<Cell, _, 1>(lines(&["...", ".S#"]), ['S']).unwrap();
+
+        assert_eq!(start, (1, 1));
+        assert_eq!(grid[(1, 1)], Cell::Empty);
+    }
+
+    #[test]
+    fn test_parse_grid_with_missing_marker() {
+        let err = parse_grid_with_markers::<Cell, _, 1>(lines(&["...", "..#"]), ['S']).unwrap_err();
+
+        assert_eq!(err.message(), "Marker 'S' does not occur in the grid");
+    }
+
+    #[test]
+    fn test_parse_grid_with_duplicate_marker() {
+        let err = parse_grid_with_markers::<Cell, _, 1>(lines(&["S..", "..S"]), ['S']).unwrap_err();
+
+        assert_eq!(
+            err.kind(),
+            &ErrorKind::DuplicateMarker {
+                marker: 'S',
+                pos: (1, 2)
+            }
+        );
+        assert_eq!(err.message(), "Duplicate marker 'S'");
+        assert_eq!(err.line_nr(), Some(2));
+        assert_eq!(err.column(), Some(3));
+    }
+
+    #[test]
+    fn test_parse_grid_with_invalid_cell() {
+        let err = parse_grid::<Cell, _>(lines(&["...", ".x."])).unwrap_err();
+
+        assert_eq!(
+            err.kind(),
+            &ErrorKind::InvalidCell {
+                ch: 'x',
+                pos: (1, 1)
+            }
+        );
+        assert_eq!(err.line_nr(), Some(2));
+        assert_eq!(err.column(), Some(2));
+    }
+
+    #[test]
+    fn test_parse_ragged_grid() {
+        let err = parse_grid::<Cell, _>(lines(&["...", "..", "..."])).unwrap_err();
+
+        assert_eq!(
+            err.kind(),
+            &ErrorKind::RowLength {
+                expected: 3,
+                found: 2
+            }
+        );
+        assert_eq!(
+            err.message(),
+            "Expected a row of 3 cells, but found 2 cells"
+        );
+        assert_eq!(err.line_nr(), Some(2));
+    }
+
+    #[test]
+    fn test_display_grid() {
+        let grid = parse_grid::<char, _>(lines(&["ab", "cd"])).unwrap();
+
+        assert_eq!(display_grid(&grid).to_string(), "ab\ncd");
+    }
+}
@@ -1,5 +1,8 @@
 //! Helper to read Advent of Code input files.
 //!
+//! Input is usually read from a file, but can also be read from stdin (by passing `-` as the path),
+//! or from a string (which is mostly useful in tests).
+//!
 //! # Usage
 //!
 //! ```
@@ -14,101 +17,415 @@
 //!     let parsed = input.parse_lines(u32::from_str).collect::<Vec<u32>>();
 //! }
 //! ```
+//!
+//! Every method that terminates the process on failure has a fallible counterpart prefixed with
+//! `try_`, which returns the error instead. These are useful in tests, or when multiple inputs are
+//! processed in a single run.
+//!
+//! ```
+//! use rdcl_aoc_core::input::InputReader;
+//!
+//! use std::str::FromStr;
+//!
+//! fn example() -> rdcl_aoc_core::ParseResult<u32> {
+//!     let input = InputReader::from("path/to/input/file");
+//!     let mut sum = 0;
+//!     for value in input.try_parse_lines(u32::from_str)? {
+//!         sum += value?;
+//!     }
+//!     Ok(sum)
+//! }
+//! ```
 
 use core::fmt;
+use std::cell::RefCell;
 use std::fs::File;
-use std::io::{BufRead, BufReader};
-use std::path::Path;
+use std::io;
+use std::io::{BufRead, BufReader, Cursor, Read};
+use std::path::{Path, PathBuf};
 use std::process::exit;
+use std::rc::Rc;
+
+#[cfg(feature = "grid")]
+use grid::Grid;
 
+use crate::error::{ErrorKind, ParseError};
+#[cfg(feature = "grid")]
+use crate::grid::{GridCell, GridWithMarkers};
 use crate::ParseResult;
 
-/// Contains the location of the input file and allows for operations to be performed on said file.
-pub struct InputReader<P: AsRef<Path>> {
-    input: P,
+/// The path which indicates that the input should be read from stdin.
+pub const STDIN: &str = "-";
+
+/// Contains the source of the input and allows for operations to be performed on said input.
+///
+/// The input can be read from a file, from stdin, from an in-memory string, or from any other
+/// type that implements `BufRead`. Files and strings can be read multiple times, other sources
+/// can only be read once.
+pub struct InputReader {
+    source: Source,
+    name: String,
+}
+
+enum Source {
+    File(PathBuf),
+    Text(SharedText),
+    Reader(RefCell<Option<Box<dyn BufRead>>>),
 }
 
-impl<P: AsRef<Path>> InputReader<P> {
-    /// Construct a new input reader from a given input path
-    pub fn from(input: P) -> InputReader<P> {
-        InputReader { input }
+impl InputReader {
+    /// Construct a new input reader from a given input path. If the path is `-`, the input is
+    /// read from stdin instead.
+    pub fn from<P: AsRef<Path>>(input: P) -> InputReader {
+        let path = input.as_ref();
+        if path == Path::new(STDIN) {
+            InputReader::from_reader(io::stdin().lock()).with_name("<stdin>")
+        } else {
+            InputReader {
+                source: Source::File(path.to_path_buf()),
+                name: path.display().to_string(),
+            }
+        }
+    }
+
+    /// Construct a new input reader which reads from a string. This is useful in tests, see also
+    /// [`include_input!`](crate::include_input).
+    pub fn from_text(text: impl Into<Rc<str>>) -> InputReader {
+        InputReader {
+            source: Source::Text(SharedText(text.into())),
+            name: "<text>".to_string(),
+        }
+    }
+
+    /// Construct a new input reader which reads from an arbitrary reader. Note that the input can
+    /// only be read once.
+    pub fn from_reader<R: BufRead + 'static>(reader: R) -> InputReader {
+        InputReader {
+            source: Source::Reader(RefCell::new(Some(Box::new(reader)))),
+            name: "<reader>".to_string(),
+        }
+    }
+
+    /// Sets the name of the input, which is used in error messages.
+    pub fn with_name(mut self, name: impl Into<String>) -> InputReader {
+        self.name = name.into();
+        self
+    }
+
+    /// The name of the input, which is used in error messages.
+    pub fn name(&self) -> &str {
+        &self.name
     }
 
     /// Reads the lines from the file
     pub fn read_lines(&self) -> impl Iterator<Item = String> {
-        BufReader::new(self.open_file()).lines().map(|s| match s {
-            Ok(s) => s,
-            Err(e) => {
-                eprintln!("Failed to read line: {}", e);
-                exit(1);
-            }
-        })
+        or_exit(self.try_read_lines(), "Failed to open file")
+            .map(|line| or_exit(line, "Failed to read line"))
+    }
+
+    /// Reads the lines from the file. Errors are annotated with the path of the file and, where
+    /// applicable, the line number.
+    pub fn try_read_lines(&self) -> io::Result<impl Iterator<Item = io::Result<String>>> {
+        let path = self.name.clone();
+        let lines = self.try_open()?.lines();
+
+        Ok(lines.enumerate().map(move |(idx, line)| {
+            line.map_err(|e| with_context(e, format!("{}:{}", path, idx + 1)))
+        }))
     }
 
     /// Reads a single line from the file. Useful if the input is a single line.
     pub fn read_line(&self) -> String {
-        match BufReader::new(self.open_file()).lines().next() {
-            Some(Ok(line)) => line,
-            Some(Err(e)) => {
-                eprintln!("Failed to read line: {}", e);
-                exit(1);
-            }
-            None => {
-                eprintln!("Input file is empty");
-                exit(1);
-            }
+        or_exit(self.try_read_line(), "Failed to read line")
+    }
+
+    /// Reads a single line from the file. Fails if the file is empty.
+    pub fn try_read_line(&self) -> io::Result<String> {
+        match self.try_read_lines()?.next() {
+            Some(line) => line,
+            None => Err(with_context(
+                io::Error::new(io::ErrorKind::UnexpectedEof, "input file is empty"),
+                self.name.clone(),
+            )),
         }
     }
 
     /// Reads the lines from the file and transforms them with the provided parser
     pub fn parse_lines<T, F, E>(&self, parser: F) -> impl Iterator<Item = T>
     where
-        F: Fn(&str) -> Result<T, E> + 'static,
-        E: fmt::Debug,
+        F: Fn(&str) -> Result<T, E>,
+        E: Into<ParseError>,
     {
-        self.read_lines().map(move |line| match parser(&line) {
+        let lines = self.try_parse_lines(parser);
+        or_exit(lines, "Failed to open file").map(|line| match line {
             Ok(v) => v,
             Err(e) => {
-                eprintln!("Failed to parse line '{}': {:?}", line, e);
+                eprintln!("{}", parse_line_failure(&e));
                 exit(1);
             }
         })
     }
 
+    /// Reads the lines from the file and transforms them with the provided parser. Every item
+    /// produced by the iterator is either the parsed value, or an error that records the path of
+    /// the file, the line number and the offending line (see [`ParseError::line`]).
+    pub fn try_parse_lines<T, F, E>(
+        &self,
+        parser: F,
+    ) -> ParseResult<impl Iterator<Item = ParseResult<T>>>
+    where
+        F: Fn(&str) -> Result<T, E>,
+        E: Into<ParseError>,
+    {
+        let path = self.name.clone();
+        let lines = self.try_read_lines()?;
+
+        Ok(lines.enumerate().map(move |(idx, line)| {
+            let line = line?;
+            parser(&line).map_err(|e| {
+                e.into()
+                    .with_line(idx + 1, &line)
+                    .with_input_name(path.as_str())
+            })
+        }))
+    }
+
     /// Reads and parses all lines in the file
     pub fn parse<T: FromInput>(&self) -> T {
-        match T::parse(self.read_lines()) {
-            Ok(v) => v,
-            Err(e) => {
-                eprintln!("Failed to parse input: {:?}", e);
-                exit(1);
-            }
-        }
+        or_exit(self.try_parse(), "Failed to parse input")
+    }
+
+    /// Reads and parses all lines in the file, without terminating the process on failure.
+    pub fn try_parse<T: FromInput>(&self) -> ParseResult<T> {
+        self.try_stream_lines(|lines| T::parse(lines))
     }
 
     /// Reads and parses all lines in the file
     pub fn parse_vec<T: VecFromInput>(&self) -> Vec<T> {
-        match T::parse(self.read_lines()) {
-            Ok(v) => v,
-            Err(e) => {
-                eprintln!("Failed to parse input: {:?}", e);
-                exit(1);
-            }
+        or_exit(self.try_parse_vec(), "Failed to parse input")
+    }
+
+    /// Reads and parses all lines in the file, without terminating the process on failure.
+    pub fn try_parse_vec<T: VecFromInput>(&self) -> ParseResult<Vec<T>> {
+        self.try_stream_lines(|lines| T::parse(lines))
+    }
+
+    /// Reads the file as sections, which are groups of lines separated by empty lines.
+    pub fn read_sections(&self) -> Sections<impl Iterator<Item = String>> {
+        Sections::from(self.read_lines())
+    }
+
+    /// Reads and parses all sections in the file.
+    pub fn parse_sections<T: FromSections>(&self) -> T {
+        or_exit(self.try_parse_sections(), "Failed to parse input")
+    }
+
+    /// Reads and parses all sections in the file, without terminating the process on failure.
+    pub fn try_parse_sections<T: FromSections>(&self) -> ParseResult<T> {
+        self.try_stream_lines(|lines| T::parse(&mut Sections::from(lines)))
+    }
+
+    /// Reads the entire input into memory, so that its lines can be borrowed instead of being
+    /// allocated one by one.
+    pub fn read_buffer(&self) -> InputBuffer {
+        or_exit(self.try_read_buffer(), "Failed to read input")
+    }
+
+    /// Reads the entire input into memory, without terminating the process on failure.
+    pub fn try_read_buffer(&self) -> io::Result<InputBuffer> {
+        let mut text = String::new();
+        self.try_open()?
+            .read_to_string(&mut text)
+            .map_err(|e| with_context(e, self.name.clone()))?;
+
+        Ok(InputBuffer {
+            text,
+            name: self.name.clone(),
+        })
+    }
+
+    /// Opens the input for reading.
+    pub fn try_open(&self) -> io::Result<Box<dyn BufRead>> {
+        match &self.source {
+            Source::File(path) => match File::open(path) {
+                Ok(file) => Ok(Box::new(BufReader::new(file))),
+                Err(e) => Err(with_context(e, self.name.clone())),
+            },
+            Source::Text(text) => Ok(Box::new(Cursor::new(text.clone()))),
+            Source::Reader(reader) => reader.borrow_mut().take().ok_or_else(|| {
+                with_context(
+                    io::Error::other("input can only be read once"),
+                    self.name.clone(),
+                )
+            }),
         }
     }
 
-    /// Opens the file for reading
-    fn open_file(&self) -> File {
-        match File::open(&self.input) {
-            Ok(file) => file,
+    /// Streams the lines to a parser. If a line could not be read, the parser sees the input as
+    /// if it ended there, and the I/O error is returned instead of the result of the parser.
+    fn try_stream_lines<T, F>(&self, parse: F) -> ParseResult<T>
+    where
+        F: FnOnce(&mut dyn Iterator<Item = String>) -> ParseResult<T>,
+    {
+        let mut error = None;
+        let mut lines = self.try_read_lines()?.map_while(|line| match line {
+            Ok(line) => Some(line),
             Err(e) => {
-                eprintln!("Failed to open file: {}", e);
-                exit(1);
+                error = Some(e);
+                None
             }
+        });
+        let result = parse(&mut lines);
+        drop(lines);
+
+        match error {
+            Some(e) => Err(e.into()),
+            None => result.map_err(|e| self.with_path(e)),
+        }
+    }
+
+    /// Annotates an error with the path of the input file.
+    fn with_path(&self, err: ParseError) -> ParseError {
+        err.with_input_name(self.name.clone())
+    }
+}
+
+/// The entire contents of an input, which allows for zero-copy iteration over its lines.
+///
+/// ```
+/// use rdcl_aoc_core::input::InputReader;
+///
+/// let buffer = InputReader::from_text("1,2\n3,4\n").read_buffer();
+/// let lines = buffer.lines().collect::<Vec<&str>>();
+/// assert_eq!(lines, vec!["1,2", "3,4"]);
+/// ```
+pub struct InputBuffer {
+    text: String,
+    name: String,
+}
+
+impl InputBuffer {
+    /// The name of the input this buffer was read from.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The contents of the input.
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+
+    /// The contents of the input as raw bytes. This can be combined with the helpers from
+    /// [`crate::bytes`].
+    pub fn as_bytes(&self) -> &[u8] {
+        self.text.as_bytes()
+    }
+
+    /// Iterates over the lines in the input, without allocating.
+    pub fn lines(&self) -> impl Iterator<Item = &str> {
+        self.text.lines()
+    }
+
+    /// Transforms the lines in the input with the provided parser, which may borrow from the
+    /// lines. Errors mention the name of the input, the line number and the offending line.
+    pub fn parse_lines<'a, T, F, E>(
+        &'a self,
+        mut parser: F,
+    ) -> impl Iterator<Item = ParseResult<T>> + 'a
+    where
+        F: FnMut(&'a str) -> Result<T, E> + 'a,
+        E: Into<ParseError>,
+    {
+        self.lines().enumerate().map(move |(idx, line)| {
+            parser(line).map_err(|e| {
+                e.into()
+                    .with_line(idx + 1, line)
+                    .with_input_name(self.name.as_str())
+            })
+        })
+    }
+}
+
+#[cfg(feature = "grid")]
+impl InputReader {
+    /// Reads the file as a grid of characters, which are converted into cells of type `T`.
+    pub fn parse_grid<T: GridCell>(&self) -> Grid<T> {
+        or_exit(self.try_parse_grid(), "Failed to parse input")
+    }
+
+    /// Reads the file as a grid of characters, without terminating the process on failure.
+    pub fn try_parse_grid<T: GridCell>(&self) -> ParseResult<Grid<T>> {
+        self.try_parse()
+    }
+
+    /// Reads the file as a grid of characters, and returns the positions of the given markers.
+    pub fn parse_grid_with_markers<T: GridCell, const N: usize>(
+        &self,
+        markers: [char; N],
+    ) -> GridWithMarkers<T, N> {
+        or_exit(
+            self.try_parse_grid_with_markers(markers),
+            "Failed to parse input",
+        )
+    }
+
+    /// Reads the file as a grid of characters, and returns the positions of the given markers,
+    /// without terminating the process on failure.
+    pub fn try_parse_grid_with_markers<T: GridCell, const N: usize>(
+        &self,
+        markers: [char; N],
+    ) -> ParseResult<GridWithMarkers<T, N>> {
+        self.try_stream_lines(|lines| crate::grid::parse_grid_with_markers(lines, markers))
+    }
+}
+
+/// Creates an [`InputReader`] for a file which is embedded in the binary using `include_str!`.
+/// The path is relative to the file in which the macro is used, so in a test for a specific day
+/// the test input can be read using `include_input!("test.txt")`.
+#[macro_export]
+macro_rules! include_input {
+    ($path:literal) => {
+        $crate::input::InputReader::from_text(include_str!($path)).with_name($path)
+    };
+}
+
+/// Text that is shared between all readers that were opened on it, so that opening the input does
+/// not copy it.
+#[derive(Clone)]
+struct SharedText(Rc<str>);
+
+impl AsRef<[u8]> for SharedText {
+    fn as_ref(&self) -> &[u8] {
+        self.0.as_bytes()
+    }
+}
+
+/// Adds context (e.g. the path of the file) to an I/O error, while preserving its kind.
+fn with_context(err: io::Error, context: String) -> io::Error {
+    io::Error::new(err.kind(), format!("{}: {}", context, err))
+}
+
+/// Unwraps a result, or terminates the process after printing the error. Uses the alternate
+/// format, so parse errors are printed as a diagnostic.
+fn or_exit<T, E: fmt::Display>(result: Result<T, E>, message: &str) -> T {
+    match result {
+        Ok(v) => v,
+        Err(e) => {
+            eprintln!("{}: {:#}", message, e);
+            exit(1);
         }
     }
 }
 
+/// Describes a line that could not be parsed. The diagnostic only shows the offending line if the
+/// error points at a span within it, so otherwise the line is included in the message.
+fn parse_line_failure(err: &ParseError) -> String {
+    match (err.line(), err.span()) {
+        (Some(line), None) => format!("Failed to parse line '{}': {:#}", line, err),
+        _ => format!("Failed to parse line: {:#}", err),
+    }
+}
+
 /// Types that can be constructed by reading the entire input.
 pub trait FromInput: Sized {
     fn parse<T>(input: T) -> ParseResult<Self>
@@ -121,3 +438,412 @@ pub trait VecFromInput: Sized {
     where
         T: Iterator<Item = String>;
 }
+
+/// Types that can be constructed from the sections of the input. A section is a group of lines,
+/// which is separated from the other sections by one or more empty lines.
+///
+/// Inputs that consist of a header, followed by a number of records, can be parsed by taking the
+/// sections one by one:
+///
+/// ```
+/// use rdcl_aoc_core::input::{FromInput, FromSections, Sections};
+/// use rdcl_aoc_core::ParseResult;
+///
+/// struct Almanac {
+///     seeds: Vec<u64>,
+///     mappings: Vec<Mapping>,
+/// }
+///
+/// struct Mapping(Vec<String>);
+///
+/// impl FromInput for Mapping {
+///     fn parse<T>(input: T) -> ParseResult<Self>
+///     where
+///         T: Iterator<Item = String>,
+///     {
+///         Ok(Mapping(input.collect()))
+///     }
+/// }
+///
+/// impl FromSections for Almanac {
+///     fn parse<I>(sections: &mut Sections<I>) -> ParseResult<Self>
+///     where
+///         I: Iterator<Item = String>,
+///     {
+///         let header = sections.next_section()?;
+///         let seeds = header[0]
+///             .split_ascii_whitespace()
+///             .skip(1)
+///             .map(|v| v.parse::<u64>())
+///             .collect::<Result<_, _>>()?;
+///         let mappings = sections.parse_rest()?;
+///
+///         Ok(Almanac { seeds, mappings })
+///     }
+/// }
+/// ```
+pub trait FromSections: Sized {
+    fn parse<I>(sections: &mut Sections<I>) -> ParseResult<Self>
+    where
+        I: Iterator<Item = String>;
+}
+
+/// Every section is parsed as a separate value.
+impl<T: FromInput> FromSections for Vec<T> {
+    fn parse<I>(sections: &mut Sections<I>) -> ParseResult<Self>
+    where
+        I: Iterator<Item = String>,
+    {
+        sections.parse_rest()
+    }
+}
+
+/// Iterator over the sections of an input. A section is a group of lines, which is separated from
+/// the other sections by one or more empty lines.
+pub struct Sections<I: Iterator<Item = String>> {
+    lines: I,
+}
+
+impl<I: Iterator<Item = String>> Sections<I> {
+    /// Splits a sequence of lines into sections.
+    pub fn from(lines: I) -> Sections<I> {
+        Sections { lines }
+    }
+
+    /// Returns the next section, or an error if there are no more sections.
+    pub fn next_section(&mut self) -> ParseResult<Vec<String>> {
+        self.next().ok_or_else(|| {
+            ParseError::from(ErrorKind::UnexpectedEnd {
+                expected: "another section".to_string(),
+            })
+        })
+    }
+
+    /// Parses the next section.
+    pub fn parse_next<T: FromInput>(&mut self) -> ParseResult<T> {
+        T::parse(self.next_section()?.into_iter())
+    }
+
+    /// Parses the next section as a list of records.
+    pub fn parse_next_vec<T: VecFromInput>(&mut self) -> ParseResult<Vec<T>> {
+        T::parse(self.next_section()?.into_iter())
+    }
+
+    /// Parses every remaining section as a separate value.
+    pub fn parse_rest<T: FromInput>(&mut self) -> ParseResult<Vec<T>> {
+        self.map(|section| T::parse(section.into_iter())).collect()
+    }
+}
+
+impl<I: Iterator<Item = String>> Iterator for Sections<I> {
+    type Item = Vec<String>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let section = self
+            .lines
+            .by_ref()
+            .skip_while(|line| line.is_empty())
+            .take_while(|line| !line.is_empty())
+            .collect::<Vec<_>>();
+
+        if section.is_empty() {
+            None
+        } else {
+            Some(section)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::error::ErrorKind;
+    use crate::parser::Parser;
+
+    use super::*;
+
+    const NUMBERS: &str = "./test-data/numbers.txt";
+    const INVALID: &str = "./test-data/invalid-numbers.txt";
+    const SECTIONS: &str = "./test-data/sections.txt";
+    const MISSING: &str = "./test-data/does-not-exist.txt";
+
+    #[test]
+    fn test_try_read_lines() {
+        let lines = InputReader::from(NUMBERS)
+            .try_read_lines()
+            .unwrap()
+            .collect::<io::Result<Vec<String>>>()
+            .unwrap();
+
+        assert_eq!(lines, vec!["1", "2", "3"]);
+    }
+
+    #[test]
+    fn test_try_read_lines_missing_file() {
+        let err = InputReader::from(MISSING).try_read_lines().err().unwrap();
+
+        assert_eq!(err.kind(), io::ErrorKind::NotFound);
+        assert!(err.to_string().starts_with(MISSING));
+    }
+
+    #[test]
+    fn test_try_read_line() {
+        let line = InputReader::from(NUMBERS).try_read_line().unwrap();
+
+        assert_eq!(line, "1");
+    }
+
+    #[test]
+    fn test_try_parse_lines() {
+        let parsed = InputReader::from(INVALID)
+            .try_parse_lines(u32::from_str)
+            .unwrap()
+            .collect::<Vec<_>>();
+
+        assert_eq!(parsed[0], Ok(1));
+        assert_eq!(
+            parsed[1].as_ref().unwrap_err().to_string(),
+            "./test-data/invalid-numbers.txt:2: invalid digit found in string"
+        );
+        assert_eq!(parsed[2], Ok(3));
+    }
+
+    #[test]
+    fn test_try_parse_lines_diagnostic() {
+        let err = InputReader::from(INVALID)
+            .try_parse_lines(|line| Parser::new(line).take_value::<u32>())
+            .unwrap()
+            .find_map(|v| v.err())
+            .unwrap();
+
+        assert_eq!(
+            err.diagnostic(),
+            "\
+error: Could not parse value 'two' with error 'ParseIntError { kind: InvalidDigit }'
+ --> ./test-data/invalid-numbers.txt:2:1
+  |
+2 | two
+  | ^^^"
+        );
+    }
+
+    #[test]
+    fn test_parse_line_failure() {
+        let err = InputReader::from(INVALID)
+            .try_parse_lines(u32::from_str)
+            .unwrap()
+            .find_map(|v| v.err())
+            .unwrap();
+
+        assert_eq!(
+            parse_line_failure(&err),
+            "\
+Failed to parse line 'two': error: invalid digit found in string
+ --> ./test-data/invalid-numbers.txt:2"
+        );
+    }
+
+    #[test]
+    fn test_parse_line_failure_with_span() {
+        let err = InputReader::from(INVALID)
+            .try_parse_lines(|line| Parser::new(line).take_value::<u32>())
+            .unwrap()
+            .find_map(|v| v.err())
+            .unwrap();
+
+        assert_eq!(
+            parse_line_failure(&err),
+            "\
+Failed to parse line: error: Could not parse value 'two' with error 'ParseIntError { kind: InvalidDigit }'
+ --> ./test-data/invalid-numbers.txt:2:1
+  |
+2 | two
+  | ^^^"
+        );
+    }
+
+    #[test]
+    fn test_try_parse() {
+        let sum = InputReader::from(NUMBERS).try_parse::<Sum>().unwrap();
+        assert_eq!(sum, Sum(6));
+
+        let err = InputReader::from(INVALID).try_parse::<Sum>().unwrap_err();
+        assert!(err.to_string().starts_with(INVALID));
+
+        assert!(InputReader::from(MISSING).try_parse::<Sum>().is_err());
+    }
+
+    #[test]
+    fn test_try_parse_vec() {
+        let parsed = InputReader::from(NUMBERS).try_parse_vec::<Sum>().unwrap();
+
+        assert_eq!(parsed, vec![Sum(1), Sum(2), Sum(3)]);
+    }
+
+    #[test]
+    fn test_sections() {
+        let lines = ["", "1", "2", "", "", "3", ""].map(String::from);
+        let sections = Sections::from(lines.into_iter()).collect::<Vec<_>>();
+
+        assert_eq!(sections, vec![vec!["1", "2"], vec!["3"]]);
+    }
+
+    #[test]
+    fn test_try_parse_sections() {
+        let sums = InputReader::from(SECTIONS)
+            .try_parse_sections::<Vec<Sum>>()
+            .unwrap();
+
+        assert_eq!(sums, vec![Sum(3), Sum(12)]);
+    }
+
+    #[test]
+    fn test_try_parse_sections_header_and_records() {
+        struct Input(Sum, Vec<Sum>);
+
+        impl FromSections for Input {
+            fn parse<I>(sections: &mut Sections<I>) -> ParseResult<Self>
+            where
+                I: Iterator<Item = String>,
+            {
+                let header = sections.parse_next()?;
+                let records = sections.parse_next_vec()?;
+                Ok(Input(header, records))
+            }
+        }
+
+        let Input(header, records) = InputReader::from(SECTIONS).try_parse_sections().unwrap();
+        assert_eq!(header, Sum(3));
+        assert_eq!(records, vec![Sum(3), Sum(4), Sum(5)]);
+
+        let err = InputReader::from(NUMBERS)
+            .try_parse_sections::<Input>()
+            .err()
+            .unwrap();
+        assert_eq!(
+            err.to_string(),
+            "./test-data/numbers.txt: Unexpected end of input, expected another section."
+        );
+    }
+
+    #[test]
+    fn test_from_text() {
+        let input = InputReader::from_text("1\n2\n3\n");
+
+        assert_eq!(input.read_lines().collect::<Vec<_>>(), vec!["1", "2", "3"]);
+        assert_eq!(input.parse::<Sum>(), Sum(6));
+    }
+
+    #[test]
+    fn test_from_text_error_location() {
+        let err = InputReader::from_text("1\n2\nthree")
+            .with_name("test.txt")
+            .try_parse_lines(u32::from_str)
+            .unwrap()
+            .find_map(|v| v.err())
+            .unwrap();
+
+        assert_eq!(err.input_name(), Some("test.txt"));
+        assert_eq!(err.line_nr(), Some(3));
+    }
+
+    #[test]
+    fn test_from_reader() {
+        let input = InputReader::from_reader(io::Cursor::new("1\n2\n"));
+
+        assert_eq!(input.read_lines().collect::<Vec<_>>(), vec!["1", "2"]);
+        assert_eq!(
+            input.try_read_line().unwrap_err().to_string(),
+            "<reader>: input can only be read once"
+        );
+    }
+
+    #[test]
+    fn test_include_input() {
+        let input = include_input!("../test-data/numbers.txt");
+
+        assert_eq!(input.name(), "../test-data/numbers.txt");
+        assert_eq!(input.read_lines().collect::<Vec<_>>(), vec!["1", "2", "3"]);
+    }
+
+    #[test]
+    fn test_try_read_buffer() {
+        let buffer = InputReader::from(NUMBERS).try_read_buffer().unwrap();
+
+        assert_eq!(buffer.lines().collect::<Vec<_>>(), vec!["1", "2", "3"]);
+        assert_eq!(buffer.as_bytes(), b"1\n2\n3\n");
+    }
+
+    #[test]
+    fn test_buffer_parse_lines_borrows() {
+        let buffer = InputReader::from_text("a=1\nb=2\n").read_buffer();
+        let pairs = buffer
+            .parse_lines(|line| line.split_once('=').ok_or(()))
+            .collect::<ParseResult<Vec<(&str, &str)>>>()
+            .unwrap();
+
+        assert_eq!(pairs, vec![("a", "1"), ("b", "2")]);
+    }
+
+    #[test]
+    fn test_buffer_parse_lines_error_location() {
+        let buffer = InputReader::from(INVALID).read_buffer();
+        let err = buffer
+            .parse_lines(u32::from_str)
+            .find_map(|v| v.err())
+            .unwrap();
+
+        assert_eq!(err.input_name(), Some(INVALID));
+        assert_eq!(err.line_nr(), Some(2));
+        assert_eq!(err.line(), Some("two"));
+    }
+
+    #[derive(Debug, Eq, PartialEq)]
+    struct First(String);
+
+    impl FromInput for First {
+        fn parse<T>(mut input: T) -> ParseResult<Self>
+        where
+            T: Iterator<Item = String>,
+        {
+            Ok(First(input.next().unwrap_or_default()))
+        }
+    }
+
+    #[test]
+    fn test_try_parse_streams_lines() {
+        // the second line is not valid UTF-8, but it is never read
+        let input = InputReader::from_reader(Cursor::new(b"1\n\xff\n".to_vec()));
+        assert_eq!(input.try_parse::<First>().unwrap(), First("1".to_string()));
+
+        let input = InputReader::from_reader(Cursor::new(b"1\n\xff\n".to_vec()));
+        let err = input.try_parse::<Sum>().unwrap_err();
+        assert!(matches!(err.kind(), ErrorKind::Io(_)));
+    }
+
+    #[derive(Debug, Eq, PartialEq)]
+    struct Sum(u32);
+
+    impl FromInput for Sum {
+        fn parse<T>(input: T) -> ParseResult<Self>
+        where
+            T: Iterator<Item = String>,
+        {
+            let mut sum = 0;
+            for line in input {
+                sum += line.parse::<u32>()?;
+            }
+            Ok(Sum(sum))
+        }
+    }
+
+    impl VecFromInput for Sum {
+        fn parse<T>(input: T) -> ParseResult<Vec<Self>>
+        where
+            T: Iterator<Item = String>,
+        {
+            input.map(|line| Ok(Sum(line.parse()?))).collect()
+        }
+    }
+}
@@ -2,9 +2,14 @@ extern crate core;
 
 use crate::error::ParseError;
 
+pub mod bytes;
+pub mod cycle;
 pub mod error;
+#[cfg(feature = "grid")]
+pub mod grid;
 pub mod input;
 pub mod parser;
+pub mod pattern;
 
 /// Type alias for results that may contain any error
 pub type DynResult<T> = Result<T, Box<dyn std::error::Error>>;
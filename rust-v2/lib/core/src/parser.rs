@@ -3,9 +3,49 @@
 use std::collections::HashSet;
 use std::fmt::Debug;
 use std::hash::Hash;
+use std::ops::Range;
 use std::str::FromStr;
 
-use crate::{err_parse_error, ParseResult};
+use crate::error::{ErrorKind, ParseError};
+use crate::ParseResult;
+
+/// A position in the input, which can be used to backtrack.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Checkpoint(usize);
+
+/// The options that can be passed to [`Parser::alt`]. This is implemented for tuples of up to six
+/// parsers, which all produce the same type of value.
+pub trait Alternatives<'a, T> {
+    /// Tries each of the parsers in order, and returns the result of the first one that succeeds.
+    fn choose(&mut self, parser: &mut Parser<'a>) -> ParseResult<T>;
+}
+
+macro_rules! impl_alternatives {
+    ($($p:ident => $alternative:ident),+) => {
+        impl<'a, T, $($p),+> Alternatives<'a, T> for ($($p,)+)
+        where
+            $($p: FnMut(&mut Parser<'a>) -> ParseResult<T>),+
+        {
+            fn choose(&mut self, parser: &mut Parser<'a>) -> ParseResult<T> {
+                let ($($alternative,)+) = self;
+                let mut furthest = None;
+                $(
+                    if let Some(v) = parser.attempt($alternative, &mut furthest) {
+                        return Ok(v);
+                    }
+                )+
+                Err(furthest.expect("there is at least one alternative"))
+            }
+        }
+    };
+}
+
+impl_alternatives!(P1 => p1);
+impl_alternatives!(P1 => p1, P2 => p2);
+impl_alternatives!(P1 => p1, P2 => p2, P3 => p3);
+impl_alternatives!(P1 => p1, P2 => p2, P3 => p3, P4 => p4);
+impl_alternatives!(P1 => p1, P2 => p2, P3 => p3, P4 => p4, P5 => p5);
+impl_alternatives!(P1 => p1, P2 => p2, P3 => p3, P4 => p4, P5 => p5, P6 => p6);
 
 pub struct Parser<'a> {
     input: &'a str,
@@ -27,17 +67,19 @@ impl<'a> Parser<'a> {
 
     pub fn skip_text(&mut self, text: &str) -> ParseResult<()> {
         if self.exhausted() {
-            err_parse_error!("Input string is exhausted.")
+            Err(self.unexpected_end(format!("'{}'", text)))
         } else if self.rest().starts_with(text) {
             self.pos += text.len();
             Ok(())
         } else {
-            err_parse_error!(
-                "Input '{}' does not contain text '{}' at position {}",
-                self.input,
-                text,
-                self.pos
-            )
+            let span = self.pos..self.pos + self.prefix_len(text.len());
+            Err(self.error_at(
+                span,
+                ErrorKind::UnexpectedText {
+                    expected: format!("'{}'", text),
+                    found: format!("'{}'", self.rest()),
+                },
+            ))
         }
     }
 
@@ -47,19 +89,14 @@ impl<'a> Parser<'a> {
         <T as FromStr>::Err: Debug,
     {
         if self.exhausted() {
-            err_parse_error!("Input string is exhausted.")
+            Err(self.unexpected_end("a value"))
         } else {
-            match self.rest().parse::<T>() {
-                Ok(v) => {
-                    self.pos = self.input.len();
-                    Ok(v)
-                }
-                Err(e) => err_parse_error!(
-                    "Could not parse value '{}' with error '{:?}'",
-                    self.rest(),
-                    e
-                ),
-            }
+            let start = self.pos;
+            self.pos = self.input.len();
+            let input = self.input;
+            self.parse_span(start, &input[start..]).inspect_err(|_| {
+                self.pos = start;
+            })
         }
     }
 
@@ -69,32 +106,373 @@ impl<'a> Parser<'a> {
         <T as FromStr>::Err: Debug,
     {
         if self.exhausted() {
-            err_parse_error!("Input string is exhausted.")
+            Err(self.unexpected_end("a value"))
         } else {
             match self.rest().find(suffix) {
                 Some(idx) => {
-                    let value = &self.input[self.pos..self.pos + idx];
-                    match value.parse::<T>() {
+                    let start = self.pos;
+                    let input = self.input;
+                    self.pos += idx;
+                    let value = self.parse_span(start, &input[start..start + idx]);
+                    match value {
                         Ok(v) => {
-                            self.pos += idx + suffix.len();
+                            self.pos += suffix.len();
                             Ok(v)
                         }
-                        Err(e) => err_parse_error!(
-                            "Could not parse value '{}' with error '{:?}'",
-                            self.rest(),
-                            e
-                        ),
+                        Err(e) => {
+                            self.pos = start;
+                            Err(e)
+                        }
                     }
                 }
-                None => err_parse_error!(
-                    "Input '{}' does not contain suffix '{}' at position {}",
-                    self.input,
-                    suffix,
-                    self.pos
-                ),
+                None => Err(self.error_at(
+                    self.pos..self.input.len(),
+                    ErrorKind::UnexpectedText {
+                        expected: format!("a value followed by '{}'", suffix),
+                        found: format!("'{}'", self.rest()),
+                    },
+                )),
+            }
+        }
+    }
+
+    /// Returns a checkpoint, which can later be used to backtrack to the current position.
+    pub fn checkpoint(&self) -> Checkpoint {
+        Checkpoint(self.pos)
+    }
+
+    /// Backtrack to a previously created checkpoint.
+    pub fn restore(&mut self, checkpoint: Checkpoint) {
+        self.pos = checkpoint.0;
+    }
+
+    /// Returns the next character, without consuming it.
+    pub fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    /// Check if the remaining input starts with `text`, without consuming anything.
+    pub fn starts_with(&self, text: &str) -> bool {
+        self.rest().starts_with(text)
+    }
+
+    /// Succeeds only if the entire input has been consumed.
+    pub fn expect_end(&self) -> ParseResult<()> {
+        if self.exhausted() {
+            Ok(())
+        } else {
+            Err(self.error_at(
+                self.pos..self.input.len(),
+                ErrorKind::UnexpectedText {
+                    expected: "end of input".to_string(),
+                    found: format!("'{}'", self.rest()),
+                },
+            ))
+        }
+    }
+
+    /// Consumes characters for as long as they match `predicate`. The result may be empty.
+    pub fn take_while<F>(&mut self, predicate: F) -> &'a str
+    where
+        F: Fn(char) -> bool,
+    {
+        let input = self.input;
+        let start = self.pos;
+        let len = input[start..]
+            .find(|ch| !predicate(ch))
+            .unwrap_or(input.len() - start);
+        self.pos += len;
+        &input[start..start + len]
+    }
+
+    /// Consumes characters for as long as they match `predicate`. Fails if not a single character
+    /// matches, in which case `what` is used to describe what was expected.
+    pub fn take_while1<F>(&mut self, what: &str, predicate: F) -> ParseResult<&'a str>
+    where
+        F: Fn(char) -> bool,
+    {
+        let start = self.pos;
+        let taken = self.take_while(predicate);
+        if taken.is_empty() {
+            match self.peek() {
+                Some(ch) => Err(self.error_at(
+                    start..start + ch.len_utf8(),
+                    ErrorKind::UnexpectedText {
+                        expected: what.to_string(),
+                        found: format!("'{}'", ch),
+                    },
+                )),
+                None => Err(self.unexpected_end(what)),
+            }
+        } else {
+            Ok(taken)
+        }
+    }
+
+    /// Parses an unsigned integer (i.e. a sequence of ASCII digits).
+    pub fn take_unsigned<T>(&mut self) -> ParseResult<T>
+    where
+        T: FromStr,
+        <T as FromStr>::Err: Debug,
+    {
+        let start = self.pos;
+        let digits = self.take_while1("a number", |ch| ch.is_ascii_digit())?;
+        self.parse_number(start, digits)
+    }
+
+    /// Parses a signed integer (i.e. a sequence of ASCII digits, optionally preceded by a sign).
+    pub fn take_signed<T>(&mut self) -> ParseResult<T>
+    where
+        T: FromStr,
+        <T as FromStr>::Err: Debug,
+    {
+        let start = self.pos;
+        if matches!(self.peek(), Some('-') | Some('+')) {
+            self.pos += 1;
+        }
+        if let Err(e) = self.take_while1("a number", |ch| ch.is_ascii_digit()) {
+            self.pos = start;
+            return Err(e);
+        }
+        let input = self.input;
+        self.parse_number(start, &input[start..self.pos])
+    }
+
+    /// Parses an identifier, which starts with a letter or an underscore, followed by any number
+    /// of letters, digits and underscores.
+    pub fn take_identifier(&mut self) -> ParseResult<&'a str> {
+        match self.peek() {
+            Some(ch) if ch.is_alphabetic() || ch == '_' => {
+                Ok(self.take_while(|ch| ch.is_alphanumeric() || ch == '_'))
+            }
+            _ => self.take_while1("an identifier", |_| false),
+        }
+    }
+
+    /// Tries to apply `parser`. If it fails, the parser backtracks and `None` is returned.
+    pub fn optional<T, F>(&mut self, mut parser: F) -> Option<T>
+    where
+        F: FnMut(&mut Parser<'a>) -> ParseResult<T>,
+    {
+        let checkpoint = self.checkpoint();
+        match parser(self) {
+            Ok(v) => Some(v),
+            Err(_) => {
+                self.restore(checkpoint);
+                None
+            }
+        }
+    }
+
+    /// Tries each of the alternatives (a tuple of parsers) in order, and returns the result of the
+    /// first one that succeeds. If none succeed, the error of the alternative that got the
+    /// furthest is returned.
+    pub fn alt<T, A>(&mut self, mut alternatives: A) -> ParseResult<T>
+    where
+        A: Alternatives<'a, T>,
+    {
+        alternatives.choose(self)
+    }
+
+    /// Applies `parser` to the text between `open` and `close`.
+    pub fn delimited<T, F>(&mut self, open: &str, parser: F, close: &str) -> ParseResult<T>
+    where
+        F: FnOnce(&mut Parser<'a>) -> ParseResult<T>,
+    {
+        self.skip_text(open)?;
+        let value = parser(self)?;
+        self.skip_text(close)?;
+        Ok(value)
+    }
+
+    /// Applies `parser` one or more times, where each application is separated by `separator`.
+    pub fn separated_list<T, F>(&mut self, separator: &str, mut parser: F) -> ParseResult<Vec<T>>
+    where
+        F: FnMut(&mut Parser<'a>) -> ParseResult<T>,
+    {
+        let mut values = vec![parser(self)?];
+        while self.starts_with(separator) {
+            self.pos += separator.len();
+            values.push(parser(self)?);
+        }
+        Ok(values)
+    }
+
+    /// Tries to apply `parser`. If it fails, the parser backtracks, and the error is kept in
+    /// `furthest` if it got further than the errors seen before.
+    fn attempt<T, F>(&mut self, mut parser: F, furthest: &mut Option<ParseError>) -> Option<T>
+    where
+        F: FnMut(&mut Parser<'a>) -> ParseResult<T>,
+    {
+        let checkpoint = self.checkpoint();
+        match parser(self) {
+            Ok(v) => Some(v),
+            Err(e) => {
+                self.restore(checkpoint);
+                let reached = |e: &ParseError| e.span().map(|s| s.start).unwrap_or(0);
+                if furthest.as_ref().is_none_or(|f| reached(&e) > reached(f)) {
+                    *furthest = Some(e);
+                }
+                None
             }
         }
     }
+
+    /// Parses the text in the range `start..self.pos`.
+    fn parse_span<T>(&self, start: usize, text: &str) -> ParseResult<T>
+    where
+        T: FromStr,
+        <T as FromStr>::Err: Debug,
+    {
+        text.parse::<T>().map_err(|e| {
+            self.error_at(
+                start..self.pos,
+                ErrorKind::InvalidValue {
+                    text: text.to_string(),
+                    reason: format!("{:?}", e),
+                },
+            )
+        })
+    }
+
+    /// Parses the number in the range `start..self.pos`.
+    fn parse_number<T>(&self, start: usize, text: &str) -> ParseResult<T>
+    where
+        T: FromStr,
+        <T as FromStr>::Err: Debug,
+    {
+        text.parse::<T>().map_err(|e| {
+            self.error_at(
+                start..self.pos,
+                ErrorKind::InvalidNumber {
+                    text: text.to_string(),
+                    reason: format!("{:?}", e),
+                },
+            )
+        })
+    }
+
+    /// Produces an error which points at `span` within the input.
+    fn error_at(&self, span: Range<usize>, kind: ErrorKind) -> ParseError {
+        ParseError::from(kind).with_span(self.input, span)
+    }
+
+    /// Produces an error which indicates that the input ended while `expected` was expected.
+    fn unexpected_end(&self, expected: impl Into<String>) -> ParseError {
+        let kind = ErrorKind::UnexpectedEnd {
+            expected: expected.into(),
+        };
+        self.error_at(self.pos..self.pos, kind)
+    }
+
+    /// The length of the longest prefix of the remaining input which is at most `len` bytes long,
+    /// and which ends on a character boundary.
+    fn prefix_len(&self, len: usize) -> usize {
+        let rest = self.rest();
+        let mut len = len.min(rest.len());
+        while !rest.is_char_boundary(len) {
+            len -= 1;
+        }
+        len
+    }
+}
+
+/// Parse a string as a list of values separated by `separator`, and collect them into any
+/// collection that implements `FromIterator` (e.g. `Vec`, `HashSet`, `BTreeSet` or `VecDeque`).
+///
+/// Whitespace around the elements is ignored. If the separator only consists of whitespace, any
+/// amount of whitespace separates the elements. If an element cannot be parsed, the error points
+/// at the offending element and mentions its index.
+///
+/// ```
+/// use std::collections::BTreeSet;
+/// use rdcl_aoc_core::parser::parse_separated;
+///
+/// let numbers = parse_separated::<Vec<u8>, _>("1, 2, 3", ",").unwrap();
+/// assert_eq!(numbers, vec![1, 2, 3]);
+///
+/// let labels = parse_separated::<BTreeSet<String>, _>("b -> a -> c", "->").unwrap();
+/// assert_eq!(labels, BTreeSet::from(["a".to_string(), "b".to_string(), "c".to_string()]));
+/// ```
+pub fn parse_separated<C, T>(line: &str, separator: &str) -> ParseResult<C>
+where
+    C: FromIterator<T>,
+    T: FromStr,
+    <T as FromStr>::Err: Debug,
+{
+    let mut index = 0;
+    parse_separated_with(line, separator, |element| {
+        let result = element.parse::<T>().map_err(|e| {
+            ParseError::from(ErrorKind::InvalidElement {
+                index,
+                text: element.to_string(),
+                reason: format!("{:?}", e),
+            })
+        });
+        index += 1;
+        result
+    })
+}
+
+/// Like [`parse_separated`], but uses `parser` to parse the individual elements. This can be used
+/// to parse nested lists.
+///
+/// ```
+/// use rdcl_aoc_core::parser::{parse_separated, parse_separated_with};
+///
+/// let lists: Vec<Vec<u8>> =
+///     parse_separated_with("1,2,3 | 4 5 6", "|", |list| parse_separated(list, " ")
+///         .or_else(|_| parse_separated(list, ",")))
+///     .unwrap();
+/// assert_eq!(lists, vec![vec![1, 2, 3], vec![4, 5, 6]]);
+/// ```
+pub fn parse_separated_with<C, T, F>(line: &str, separator: &str, mut parser: F) -> ParseResult<C>
+where
+    C: FromIterator<T>,
+    F: FnMut(&str) -> ParseResult<T>,
+{
+    let trimmed = line.trim();
+    let elements: Box<dyn Iterator<Item = &str>> = if trimmed.is_empty() {
+        Box::new(std::iter::empty())
+    } else if separator.trim().is_empty() {
+        Box::new(trimmed.split_whitespace())
+    } else {
+        Box::new(trimmed.split(separator).map(str::trim))
+    };
+
+    elements
+        .map(|element| {
+            let offset = element.as_ptr() as usize - line.as_ptr() as usize;
+            parser(element).map_err(|e| match e.span() {
+                Some(_) if e.line() == Some(element) => e.within(line, offset),
+                _ => e.with_span(line, offset..offset + element.len()),
+            })
+        })
+        .collect()
+}
+
+/// Parse a string as a list of exactly `N` values separated by `separator`.
+///
+/// ```
+/// use rdcl_aoc_core::parser::parse_separated_array;
+///
+/// let [x, y, z] = parse_separated_array::<i32, 3>("1,-2,3", ",").unwrap();
+/// assert_eq!((x, y, z), (1, -2, 3));
+/// ```
+pub fn parse_separated_array<T, const N: usize>(line: &str, separator: &str) -> ParseResult<[T; N]>
+where
+    T: FromStr,
+    <T as FromStr>::Err: Debug,
+{
+    let values = parse_separated::<Vec<T>, T>(line, separator)?;
+    let len = values.len();
+    values.try_into().map_err(|_| {
+        ParseError::from(ErrorKind::UnexpectedText {
+            expected: format!("{} elements", N),
+            found: format!("{} elements", len),
+        })
+        .with_span(line, 0..line.len())
+    })
 }
 
 /// Parse a string as a whitespace separated list of values into a Vec.
@@ -121,6 +499,8 @@ where
 
 #[cfg(test)]
 mod tests {
+    use std::collections::{BTreeSet, VecDeque};
+
     use super::*;
 
     #[test]
@@ -148,8 +528,11 @@ mod tests {
         let err = parser.skip_text("invalid text").unwrap_err();
 
         assert_eq!(
-            &err.0,
-            "Input 'Some input' does not contain text 'invalid text' at position 0"
+            err.kind(),
+            &ErrorKind::UnexpectedText {
+                expected: "'invalid text'".to_string(),
+                found: "'Some input'".to_string(),
+            }
         );
     }
 
@@ -161,8 +544,11 @@ mod tests {
         let err = parser.take_value_upto::<u8>("invalid text").unwrap_err();
 
         assert_eq!(
-            &err.0,
-            "Input 'Some input' does not contain suffix 'invalid text' at position 0"
+            err.kind(),
+            &ErrorKind::UnexpectedText {
+                expected: "a value followed by 'invalid text'".to_string(),
+                found: "'Some input'".to_string(),
+            }
         );
     }
 
@@ -174,9 +560,248 @@ mod tests {
         let err = parser.take_value_upto::<u8>(" input").unwrap_err();
 
         assert_eq!(
-            &err.0,
-            "Could not parse value 'Some input' with error 'ParseIntError { kind: InvalidDigit }'"
+            err.kind(),
+            &ErrorKind::InvalidValue {
+                text: "Some".to_string(),
+                reason: "ParseIntError { kind: InvalidDigit }".to_string(),
+            }
+        );
+        assert_eq!(parser.rest(), input);
+    }
+
+    #[test]
+    fn test_parser_error_location() {
+        let input = "x=1, y=abc";
+        let mut parser = Parser::new(input);
+
+        parser.skip_text("x=").unwrap();
+        parser.take_value_upto::<u8>(", ").unwrap();
+        let err = parser.skip_text("z=").unwrap_err();
+        assert_eq!(err.span(), Some(5..7));
+        assert_eq!(err.column(), Some(6));
+
+        parser.skip_text("y=").unwrap();
+        let err = parser.take_value::<u8>().unwrap_err();
+        assert_eq!(err.span(), Some(7..10));
+        assert_eq!(err.line(), Some(input));
+    }
+
+    #[test]
+    fn test_parser_primitives() {
+        let mut parser = Parser::new("abc_1 -15 +3 42");
+
+        assert_eq!(parser.take_identifier(), Ok("abc_1"));
+        assert_eq!(parser.take_while(|ch| ch == ' '), " ");
+        assert_eq!(parser.take_signed::<i32>(), Ok(-15));
+        parser.skip_text(" ").unwrap();
+        assert_eq!(parser.take_signed::<i32>(), Ok(3));
+        parser.skip_text(" ").unwrap();
+        assert!(parser.take_identifier().is_err());
+        assert_eq!(parser.take_unsigned::<u8>(), Ok(42));
+        assert_eq!(parser.expect_end(), Ok(()));
+    }
+
+    #[test]
+    fn test_parser_take_unsigned_errors() {
+        let mut parser = Parser::new("x=-1");
+        parser.skip_text("x=").unwrap();
+
+        let err = parser.take_unsigned::<u8>().unwrap_err();
+        assert_eq!(
+            err.kind(),
+            &ErrorKind::UnexpectedText {
+                expected: "a number".to_string(),
+                found: "'-'".to_string(),
+            }
         );
+        assert_eq!(err.span(), Some(2..3));
+
+        let err = Parser::new("300").take_unsigned::<u8>().unwrap_err();
+        assert!(matches!(
+            err.kind(),
+            ErrorKind::InvalidNumber { text, .. } if text == "300"
+        ));
+        assert_eq!(err.span(), Some(0..3));
+    }
+
+    #[test]
+    fn test_parser_expect_end() {
+        let mut parser = Parser::new("12ab");
+        parser.take_unsigned::<u8>().unwrap();
+
+        let err = parser.expect_end().unwrap_err();
+        assert_eq!(err.message(), "Expected end of input, found 'ab'");
+        assert_eq!(err.span(), Some(2..4));
+    }
+
+    #[test]
+    fn test_parser_checkpoint() {
+        let mut parser = Parser::new("abc");
+        let checkpoint = parser.checkpoint();
+        parser.skip_text("ab").unwrap();
+        parser.restore(checkpoint);
+
+        assert_eq!(parser.rest(), "abc");
+    }
+
+    #[test]
+    fn test_parser_optional() {
+        let mut parser = Parser::new("-5");
+
+        assert_eq!(parser.optional(|p| p.skip_text("+")), None);
+        assert_eq!(parser.optional(|p| p.skip_text("-")), Some(()));
+        assert_eq!(parser.optional(|p| p.take_unsigned::<u8>()), Some(5));
+    }
+
+    #[test]
+    fn test_parser_alt() {
+        let mut parser = Parser::new("x<10");
+        let less = |p: &mut Parser| p.skip_text("<").map(|_| '<');
+        let greater = |p: &mut Parser| p.skip_text(">").map(|_| '>');
+
+        parser.skip_text("x").unwrap();
+        assert_eq!(parser.alt((greater, less)), Ok('<'));
+        assert!(parser.alt((greater, less)).is_err());
+        assert_eq!(parser.rest(), "10");
+
+        // the error of the alternative that got the furthest is returned
+        let mut parser = Parser::new("ab");
+        let err = parser
+            .alt((
+                |p: &mut Parser| p.skip_text("ab!"),
+                |p: &mut Parser| p.skip_text("x"),
+            ))
+            .unwrap_err();
+        assert_eq!(err.column(), Some(1));
+        let err = parser
+            .alt((
+                |p: &mut Parser| p.skip_text("x"),
+                |p: &mut Parser| p.skip_text("a").and_then(|_| p.skip_text("c")),
+            ))
+            .unwrap_err();
+        assert_eq!(err.column(), Some(2));
+        assert_eq!(parser.rest(), "ab");
+    }
+
+    #[test]
+    fn test_parser_workflow() {
+        #[derive(Debug, Eq, PartialEq)]
+        enum Rule<'a> {
+            Conditional(&'a str, char, u32, &'a str),
+            Always(&'a str),
+        }
+
+        fn rule<'a>(parser: &mut Parser<'a>) -> ParseResult<Rule<'a>> {
+            let conditional = |p: &mut Parser<'a>| {
+                let rating = p.take_identifier()?;
+                let operator = p.alt((
+                    |p: &mut Parser| p.skip_text("<").map(|_| '<'),
+                    |p: &mut Parser| p.skip_text(">").map(|_| '>'),
+                ))?;
+                let value = p.take_unsigned()?;
+                p.skip_text(":")?;
+                let target = p.take_identifier()?;
+                Ok(Rule::Conditional(rating, operator, value, target))
+            };
+
+            match parser.optional(conditional) {
+                Some(rule) => Ok(rule),
+                None => Ok(Rule::Always(parser.take_identifier()?)),
+            }
+        }
+
+        let mut parser = Parser::new("px{a<2006:qkq,m>2090:A,rhg}");
+        let name = parser.take_identifier().unwrap();
+        let rules = parser
+            .delimited("{", |p| p.separated_list(",", rule), "}")
+            .unwrap();
+        parser.expect_end().unwrap();
+
+        assert_eq!(name, "px");
+        assert_eq!(
+            rules,
+            vec![
+                Rule::Conditional("a", '<', 2006, "qkq"),
+                Rule::Conditional("m", '>', 2090, "A"),
+                Rule::Always("rhg"),
+            ]
+        );
+
+        let err = Parser::new("px{a<2006:qkq,m>2090:A,rhg")
+            .delimited("px{", |p| p.separated_list(",", rule), "}")
+            .unwrap_err();
+        assert_eq!(
+            err.kind(),
+            &ErrorKind::UnexpectedEnd {
+                expected: "'}'".to_string()
+            }
+        );
+        assert_eq!(err.column(), Some(27));
+    }
+
+    #[test]
+    fn test_parse_separated_collections() {
+        let line = "3, 1, 2, 1";
+
+        assert_eq!(parse_separated(line, ","), Ok(vec![3, 1, 2, 1]));
+        assert_eq!(parse_separated(line, ","), Ok(HashSet::from([1, 2, 3])));
+        assert_eq!(parse_separated(line, ","), Ok(BTreeSet::from([1, 2, 3])));
+        assert_eq!(parse_separated(line, ","), Ok(VecDeque::from([3, 1, 2, 1])));
+        assert_eq!(parse_separated_array(line, ","), Ok([3, 1, 2, 1]));
+    }
+
+    #[test]
+    fn test_parse_separated_separators() {
+        assert_eq!(parse_separated("1,2,3", ","), Ok(vec![1, 2, 3]));
+        assert_eq!(parse_separated("1 | 2 | 3", "|"), Ok(vec![1, 2, 3]));
+        assert_eq!(parse_separated("1 -> 2 -> 3", "->"), Ok(vec![1, 2, 3]));
+        assert_eq!(parse_separated("  1   2 3 ", " "), Ok(vec![1, 2, 3]));
+        assert_eq!(parse_separated::<Vec<u8>, _>("", ","), Ok(vec![]));
+    }
+
+    #[test]
+    fn test_parse_separated_nested() {
+        let parsed: ParseResult<Vec<Vec<u8>>> =
+            parse_separated_with("1 2 3 | 4 5 6", "|", |list| parse_separated(list, " "));
+
+        assert_eq!(parsed, Ok(vec![vec![1, 2, 3], vec![4, 5, 6]]));
+    }
+
+    #[test]
+    fn test_parse_separated_reports_failing_element() {
+        let err = parse_separated::<Vec<u8>, _>("1, 2, x, 4", ",").unwrap_err();
+
+        assert_eq!(
+            err.kind(),
+            &ErrorKind::InvalidElement {
+                index: 2,
+                text: "x".to_string(),
+                reason: "ParseIntError { kind: InvalidDigit }".to_string(),
+            }
+        );
+        assert_eq!(err.span(), Some(6..7));
+    }
+
+    #[test]
+    fn test_parse_separated_nested_error_location() {
+        let err = parse_separated_with::<Vec<Vec<u8>>, _, _>("1 2 | 3 x", "|", |list| {
+            parse_separated(list, " ")
+        })
+        .unwrap_err();
+
+        assert!(matches!(
+            err.kind(),
+            ErrorKind::InvalidElement { index: 1, .. }
+        ));
+        assert_eq!(err.span(), Some(8..9));
+        assert_eq!(err.line(), Some("1 2 | 3 x"));
+    }
+
+    #[test]
+    fn test_parse_separated_array_wrong_length() {
+        let err = parse_separated_array::<u8, 3>("1,2", ",").unwrap_err();
+
+        assert_eq!(err.message(), "Expected 3 elements, found 2 elements");
     }
 
     #[test]
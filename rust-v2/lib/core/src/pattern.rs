@@ -0,0 +1,244 @@
+//! Declarative parsing of lines that follow a fixed pattern.
+//!
+//! Many puzzle inputs consist of lines with some fixed text, interspersed with values. The
+//! [`parse_pattern!`](crate::parse_pattern) macro extracts these values using a template, in which
+//! every value is indicated with `{}`.
+//!
+//! # Usage
+//!
+//! ```
+//! use rdcl_aoc_core::parse_pattern;
+//!
+//! let line = "Sensor at x=2, y=18: closest beacon is at x=-2, y=15";
+//! let template = "Sensor at x={}, y={}: closest beacon is at x={}, y={}";
+//!
+//! // extract the values into a tuple
+//! let (sx, sy, bx, by) = parse_pattern!(line, template => i64, i64, i64, i64).unwrap();
+//! assert_eq!((sx, sy, bx, by), (2, 18, -2, 15));
+//!
+//! // or fill a struct, in which case the types are inferred from the fields
+//! struct Point {
+//!     x: i64,
+//!     y: i64,
+//! }
+//! let point = parse_pattern!("x=-2, y=15", "x={}, y={}" => Point { x, y }).unwrap();
+//! assert_eq!((point.x, point.y), (-2, 15));
+//! ```
+
+use std::fmt::Debug;
+use std::str::FromStr;
+
+use crate::error::ParseError;
+use crate::parser::Parser;
+use crate::ParseResult;
+
+/// The placeholder that indicates a value in a template.
+pub const PLACEHOLDER: &str = "{}";
+
+/// Matches an input against a template, one value at a time. This is used by
+/// [`parse_pattern!`](crate::parse_pattern), and normally does not need to be used directly.
+pub struct Matcher<'a, 't> {
+    template: &'t str,
+    parser: Parser<'a>,
+    literals: Vec<&'t str>,
+    next: usize,
+}
+
+impl<'a, 't> Matcher<'a, 't> {
+    /// Starts matching `input` against `template`, by consuming the text before the first value.
+    pub fn new(template: &'t str, input: &'a str) -> ParseResult<Matcher<'a, 't>> {
+        let literals = template.split(PLACEHOLDER).collect::<Vec<_>>();
+        if literals.len() < 2 {
+            return Err(ParseError::new(format!(
+                "Template '{}' does not contain any placeholders",
+                template
+            )));
+        }
+        if let Some(idx) = literals[1..literals.len() - 1]
+            .iter()
+            .position(|l| l.is_empty())
+        {
+            return Err(ParseError::new(format!(
+                "Template '{}' is ambiguous, as placeholder {} is immediately followed by another placeholder",
+                template,
+                idx + 1
+            )));
+        }
+
+        let mut matcher = Matcher {
+            template,
+            parser: Parser::new(input),
+            literals,
+            next: 0,
+        };
+        matcher.skip_literal(0)?;
+        Ok(matcher)
+    }
+
+    /// Extracts the next value, and consumes the text that follows it.
+    pub fn take<T>(&mut self) -> ParseResult<T>
+    where
+        T: FromStr,
+        <T as FromStr>::Err: Debug,
+    {
+        self.next += 1;
+        if self.next >= self.literals.len() {
+            return Err(ParseError::new(format!(
+                "Template '{}' only contains {} placeholders",
+                self.template,
+                self.literals.len() - 1
+            )));
+        }
+
+        let suffix = self.literals[self.next];
+        if suffix.is_empty() {
+            self.parser.take_value()
+        } else {
+            self.parser.take_value_upto(suffix)
+        }
+    }
+
+    /// Verifies that all values have been extracted, and that there is no remaining input.
+    pub fn finish(self) -> ParseResult<()> {
+        if self.next + 1 != self.literals.len() {
+            return Err(ParseError::new(format!(
+                "Template '{}' contains {} placeholders, but only {} values were extracted",
+                self.template,
+                self.literals.len() - 1,
+                self.next
+            )));
+        }
+        self.parser.expect_end()
+    }
+
+    fn skip_literal(&mut self, idx: usize) -> ParseResult<()> {
+        let literal = self.literals[idx];
+        if literal.is_empty() {
+            Ok(())
+        } else {
+            self.parser.skip_text(literal)
+        }
+    }
+}
+
+/// Extracts values from a string using a template, in which values are indicated by `{}`.
+///
+/// The values can either be returned as a tuple, by listing their types, or be used to construct
+/// a struct, by listing its fields (in the order in which they appear in the template). Values are
+/// parsed using `FromStr`. The result is a `ParseResult`, where any error points at the part of
+/// the input that did not match the template.
+///
+/// ```
+/// use rdcl_aoc_core::parse_pattern;
+///
+/// let (from, to) = parse_pattern!("move 1 from 2 to 1", "move 1 from {} to {}" => u8, u8).unwrap();
+/// assert_eq!((from, to), (2, 1));
+///
+/// let err = parse_pattern!("move 1 from 2 onto 1", "move 1 from {} to {}" => u8, u8).unwrap_err();
+/// assert_eq!(err.span(), Some(12..20));
+/// ```
+#[macro_export]
+macro_rules! parse_pattern {
+    ($input:expr, $template:expr => $name:ident { $($field:ident),+ $(,)? }) => {{
+        $crate::pattern::Matcher::new($template, $input).and_then(|mut matcher| {
+            let value = $name { $($field: matcher.take()?),+ };
+            matcher.finish()?;
+            Ok(value)
+        })
+    }};
+    ($input:expr, $template:expr => $($type:ty),+ $(,)?) => {{
+        $crate::pattern::Matcher::new($template, $input).and_then(|mut matcher| {
+            let value = ($(matcher.take::<$type>()?,)+);
+            matcher.finish()?;
+            Ok(value)
+        })
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::ErrorKind;
+
+    #[test]
+    fn test_tuple() {
+        let parsed = parse_pattern!("1,2,3~1,2,5", "{},{},{}~{},{},{}" => u8, u8, u8, u8, u8, u8);
+
+        assert_eq!(parsed, Ok((1, 2, 3, 1, 2, 5)));
+    }
+
+    #[test]
+    fn test_struct() {
+        #[derive(Debug, Eq, PartialEq)]
+        struct Instruction {
+            count: usize,
+            from: u8,
+            to: u8,
+        }
+
+        let parsed = parse_pattern!(
+            "move 10 from 2 to 1",
+            "move {} from {} to {}" => Instruction { count, from, to }
+        );
+
+        assert_eq!(
+            parsed,
+            Ok(Instruction {
+                count: 10,
+                from: 2,
+                to: 1
+            })
+        );
+    }
+
+    #[test]
+    fn test_mismatched_text() {
+        let err = parse_pattern!("x=1; y=2", "x={}, y={}" => i32, i32).unwrap_err();
+
+        assert_eq!(
+            err.message(),
+            "Expected a value followed by ', y=', found '1; y=2'"
+        );
+        assert_eq!(err.span(), Some(2..8));
+    }
+
+    #[test]
+    fn test_invalid_value() {
+        let err = parse_pattern!("x=1, y=b", "x={}, y={}" => i32, i32).unwrap_err();
+
+        assert!(matches!(err.kind(), ErrorKind::InvalidValue { .. }));
+        assert_eq!(err.span(), Some(7..8));
+    }
+
+    #[test]
+    fn test_trailing_input() {
+        let err = parse_pattern!("<1> and more", "<{}>" => i32).unwrap_err();
+
+        assert_eq!(err.message(), "Expected end of input, found ' and more'");
+    }
+
+    #[test]
+    fn test_placeholder_count_mismatch() {
+        assert!(parse_pattern!("1 2", "{} {}" => u8).is_err());
+        assert!(parse_pattern!("1 2", "{} {}" => u8, u8, u8).is_err());
+    }
+
+    #[test]
+    fn test_ambiguous_template() {
+        let err = parse_pattern!("12", "{}{}" => u8, u8).unwrap_err();
+
+        assert_eq!(
+            err.message(),
+            "Template '{}{}' is ambiguous, as placeholder 1 is immediately followed by another placeholder"
+        );
+    }
+
+    #[test]
+    fn test_template_without_placeholders() {
+        let err = parse_pattern!("abc", "abc" => u8).unwrap_err();
+
+        assert_eq!(
+            err.message(),
+            "Template 'abc' does not contain any placeholders"
+        );
+    }
+}
@@ -2,7 +2,9 @@
 
 use ops::{BitAnd, Shl, ShlAssign, Shr, Sub};
 use std::ops;
-use std::ops::{AddAssign, Div, Mul, Rem, RemAssign, SubAssign};
+use std::ops::{Add, AddAssign, Div, Mul, Rem, RemAssign, SubAssign};
+
+pub mod modular;
 
 /// Computes the greatest common divisor for numbers a and b.
 /// TODO: May return negative values.
@@ -29,31 +31,65 @@ where
     (a / g) * b
 }
 
-/// Solve the chinese remainder theorem for (n1, a1) and (n2, a2). We assume that:
-/// * n1 and n2 are coprime
-/// * n1 and n2 are no more than 63 bits (as they are converted to i64)
-#[allow(clippy::many_single_char_names)]
-pub fn solve_crt((n_1, a_1): (u64, u64), (n_2, a_2): (u64, u64)) -> u64 {
-    let prod = n_1 * n_2;
-    let (m_1, m_2) = bezout_coefficients(n_1 as i64, n_2 as i64);
-
-    // since n1 and n2 are coprime, either m1 or m2 is negative (but not both)
+/// Solve the chinese remainder theorem for (n1, a1) and (n2, a2), i.e. find the smallest x such
+/// that x = a1 (mod n1) and x = a2 (mod n2). See [`solve_crt_system`] for the general case.
+///
+/// Panics if there is no solution, which can only happen if n1 and n2 are not coprime, or if the
+/// solution does not fit in a u64.
+pub fn solve_crt(c_1: (u64, u64), c_2: (u64, u64)) -> u64 {
+    let (x, _) = solve_crt_system([c_1, c_2]).expect("the congruences are inconsistent");
+    x.try_into().expect("the solution does not fit in a u64")
+}
 
-    let (p_1, p_2) = (
-        crt_mod_mult_helper(a_1, m_2, n_2, prod),
-        crt_mod_mult_helper(a_2, m_1, n_1, prod),
-    );
+/// Solve a system of congruences x = a_i (mod n_i), given as pairs (n_i, a_i). The moduli do not
+/// need to be coprime. Returns the smallest non-negative solution, together with the combined
+/// modulus (the least common multiple of all n_i). All solutions are of the form x + k * modulus.
+///
+/// Returns `None` if the congruences are inconsistent (e.g. x = 1 (mod 4) and x = 2 (mod 6)).
+/// Panics if a modulus is zero, or if the combined modulus does not fit in a u128.
+///
+/// ```
+/// use rdcl_aoc_math::solve_crt_system;
+///
+/// assert_eq!(solve_crt_system([(3, 2), (5, 3), (7, 2)]), Some((23, 105)));
+/// assert_eq!(solve_crt_system([(4, 3), (6, 5)]), Some((11, 12)));
+/// assert_eq!(solve_crt_system([(4, 1), (6, 2)]), None);
+/// ```
+pub fn solve_crt_system<I>(congruences: I) -> Option<(u128, u128)>
+where
+    I: IntoIterator<Item = (u64, u64)>,
+{
+    let mut solution: (u128, u128) = (0, 1);
+    for (n, a) in congruences {
+        assert_ne!(n, 0, "a modulus must not be zero");
+        solution = combine_congruences(solution, (n as u128, a as u128 % n as u128))?;
+    }
+    Some(solution)
+}
 
-    if m_1 < 0 {
-        (p_1 + prod - p_2) % prod
+/// Combines x = x_1 (mod m) and x = a (mod n) into a single congruence. Requires x_1 < m and a < n,
+/// with n no more than 64 bits.
+fn combine_congruences((x_1, m): (u128, u128), (n, a): (u128, u128)) -> Option<(u128, u128)> {
+    let g = gcd(m, n);
+    let diff = if a >= x_1 % n {
+        a - x_1 % n
     } else {
-        (p_2 + prod - p_1) % prod
+        a + n - x_1 % n
+    };
+    if diff % g != 0 {
+        return None;
     }
-}
 
-#[allow(clippy::many_single_char_names)]
-fn crt_mod_mult_helper(a: u64, m: i64, n: u64, modulus: u64) -> u64 {
-    mul_mod(mul_mod(a, m.unsigned_abs(), modulus), n, modulus)
+    // x = x_1 + m * k, where (m / g) * k = diff / g (mod n / g)
+    let n_g = n / g;
+    let combined = (m / g)
+        .checked_mul(n)
+        .expect("the combined modulus does not fit in a u128");
+    let inverse = modular::inverse_mod(((m / g) % n_g) as u64, n_g as u64)
+        .expect("m / g and n / g are coprime") as u128;
+    let k = (diff / g) * inverse % n_g;
+
+    Some((x_1 + m * k, combined))
 }
 
 pub trait MulModCompatible:
@@ -63,6 +99,7 @@ pub trait MulModCompatible:
     + Eq
     + Ord
     + RemAssign<Self>
+    + Add<Self, Output = Self>
     + AddAssign<Self>
     + Sub<Self, Output = Self>
     + SubAssign<Self>
@@ -107,7 +144,9 @@ impl MulModCompatible for usize {
     }
 }
 
-/// Multiplies two numbers with a given modulus.
+/// Multiplies two numbers with a given modulus. Works for any modulus that fits in the type.
+///
+/// Panics if the modulus is zero.
 #[allow(clippy::many_single_char_names)]
 pub fn mul_mod<T: MulModCompatible>(mut a: T, mut b: T, modulus: T) -> T {
     // https://en.wikipedia.org/wiki/Modular_arithmetic#Example_implementations
@@ -117,21 +156,22 @@ pub fn mul_mod<T: MulModCompatible>(mut a: T, mut b: T, modulus: T) -> T {
     let magic = T::magic_number();
 
     let mut result = zero;
-    let mp2 = modulus >> one;
     a %= modulus;
     b %= modulus;
 
+    // both result and b are less than the modulus, so the comparisons below avoid overflows
     for _ in 0..T::size() {
-        result = if result > mp2 {
-            (result << one) - modulus
+        result = if result >= modulus - result {
+            result - (modulus - result)
         } else {
             result << one
         };
         if a & magic != zero {
-            result += b;
-        }
-        if result > modulus {
-            result -= modulus;
+            result = if result >= modulus - b {
+                result - (modulus - b)
+            } else {
+                result + b
+            };
         }
         a <<= one;
     }
@@ -173,6 +213,9 @@ mod tests {
         fn test_mul_mod_u64() {
             assert_eq!(mul_mod::<u64>(10, 10, 7), 2);
             assert_eq!(mul_mod(u64::MAX, 2, 7), 2);
+            assert_eq!(mul_mod::<u64>(6, 6, 9), 0);
+            assert_eq!(mul_mod(u64::MAX - 1, u64::MAX - 1, u64::MAX), 1);
+            assert_eq!(mul_mod(1 << 63, 2, u64::MAX), 1);
         }
 
         #[test]
@@ -232,6 +275,37 @@ mod tests {
         pub fn test_6() {
             assert_eq!(solve_crt((21, 13), (5, 4)), 34);
         }
+
+        #[test]
+        pub fn test_system() {
+            assert_eq!(solve_crt_system([]), Some((0, 1)));
+            assert_eq!(solve_crt_system([(7, 13)]), Some((6, 7)));
+            assert_eq!(
+                solve_crt_system([(17, 0), (13, 11), (19, 16)]),
+                Some((3417, 4199))
+            );
+            assert_eq!(
+                solve_crt_system([(1789, 0), (37, 36), (47, 45), (1889, 1886)]),
+                Some((1202161486, 5876813119))
+            );
+        }
+
+        #[test]
+        pub fn test_system_not_coprime() {
+            assert_eq!(solve_crt_system([(6, 5), (4, 3), (10, 9)]), Some((59, 60)));
+            assert_eq!(solve_crt_system([(6, 5), (4, 2)]), None);
+            assert_eq!(solve_crt_system([(6, 5), (6, 5)]), Some((5, 6)));
+        }
+
+        #[test]
+        pub fn test_system_large() {
+            // two primes close to 2^64, so the combined modulus needs more than 64 bits
+            let (p, q) = (18446744073709551557, 18446744073709551533);
+            let (x, modulus) = solve_crt_system([(p, 3), (q, 5)]).unwrap();
+            assert_eq!(modulus, p as u128 * q as u128);
+            assert_eq!(x % p as u128, 3);
+            assert_eq!(x % q as u128, 5);
+        }
     }
 
     mod bezout_coefficients {
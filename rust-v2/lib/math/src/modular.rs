@@ -0,0 +1,402 @@
+//! Modular arithmetic.
+//!
+//! All operations work on moduli of up to 64 bits. Products are computed with
+//! [`mul_mod`](crate::mul_mod), so they never overflow. A modulus of zero is not allowed, and
+//! causes a panic.
+//!
+//! # Usage
+//!
+//! ```
+//! use rdcl_aoc_math::modular::{Affine, ModInt};
+//!
+//! // the card shuffle from advent of code 2019, day 22: deal with increment 7, then cut 3
+//! let shuffle = Affine::new(7, 0, 10).then(Affine::new(1, 10 - 3, 10));
+//! assert_eq!(shuffle.apply(1), 4);
+//!
+//! // repeat the shuffle a trillion times, and find out which card ends up in position 4
+//! let repeated = shuffle.pow(1_000_000_000_000);
+//! let card = repeated.inverse().unwrap().apply(4);
+//! assert_eq!(repeated.apply(card), 4);
+//!
+//! // the handshake from advent of code 2020, day 25
+//! let subject = ModInt::new(7, 20201227);
+//! assert_eq!(ModInt::new(5764801, 20201227).log(subject), Some(8));
+//! ```
+
+use std::collections::HashMap;
+use std::fmt;
+use std::ops::{Add, Mul, Neg, Sub};
+
+use crate::mul_mod;
+
+/// Computes `base^exp mod modulus`, using exponentiation by squaring. Panics if the modulus is
+/// zero.
+pub fn pow_mod(base: u64, mut exp: u64, modulus: u64) -> u64 {
+    assert_ne!(modulus, 0, "the modulus must not be zero");
+    let mut base = base % modulus;
+    let mut result = 1 % modulus;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = mul_mod(result, base, modulus);
+        }
+        base = mul_mod(base, base, modulus);
+        exp >>= 1;
+    }
+    result
+}
+
+/// Computes the inverse of `a` modulo `modulus`, i.e. the number `x` such that `a * x = 1 (mod
+/// modulus)`. Returns `None` if `a` and `modulus` are not coprime, in which case there is no
+/// inverse. Panics if the modulus is zero.
+pub fn inverse_mod(a: u64, modulus: u64) -> Option<u64> {
+    assert_ne!(modulus, 0, "the modulus must not be zero");
+    let mut r = ((a % modulus) as i128, modulus as i128);
+    let mut s = (1, 0);
+
+    while r.1 != 0 {
+        let quotient = r.0 / r.1;
+        r = (r.1, r.0 - quotient * r.1);
+        s = (s.1, s.0 - quotient * s.1);
+    }
+
+    if r.0 == 1 || modulus == 1 {
+        Some(s.0.rem_euclid(modulus as i128) as u64)
+    } else {
+        None
+    }
+}
+
+/// Finds the smallest `k` such that `base^k = target (mod modulus)`, using the
+/// [baby-step giant-step](https://en.wikipedia.org/wiki/Baby-step_giant-step) algorithm. This
+/// takes time and memory in the order of the square root of the modulus.
+///
+/// Returns `None` if there is no such `k`, or if `base` and `modulus` are not coprime. Panics if
+/// the modulus is zero.
+pub fn discrete_log(base: u64, target: u64, modulus: u64) -> Option<u64> {
+    assert_ne!(modulus, 0, "the modulus must not be zero");
+    let target = target % modulus;
+    if target == 1 % modulus {
+        return Some(0);
+    }
+    inverse_mod(base, modulus)?;
+
+    let steps = (modulus as f64).sqrt().ceil() as u64;
+
+    // baby steps: target * base^j for every j, where larger values of j overwrite smaller ones
+    let mut baby_steps = HashMap::with_capacity(steps as usize);
+    let mut value = target;
+    for j in 0..steps {
+        baby_steps.insert(value, j);
+        value = mul_mod(value, base, modulus);
+    }
+
+    // giant steps: base^(i * steps), which equals target * base^j if k = i * steps - j
+    let giant_step = pow_mod(base, steps, modulus);
+    let mut value = 1 % modulus;
+    for i in 1..=steps {
+        value = mul_mod(value, giant_step, modulus);
+        if let Some(j) = baby_steps.get(&value) {
+            return Some(i * steps - j);
+        }
+    }
+
+    None
+}
+
+/// An integer modulo some modulus.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct ModInt {
+    value: u64,
+    modulus: u64,
+}
+
+impl ModInt {
+    /// Creates a new integer modulo `modulus`. Negative values are allowed.
+    pub fn new(value: i128, modulus: u64) -> ModInt {
+        assert_ne!(modulus, 0, "the modulus must not be zero");
+        ModInt {
+            value: value.rem_euclid(modulus as i128) as u64,
+            modulus,
+        }
+    }
+
+    /// The value, which is always less than the modulus.
+    pub fn value(&self) -> u64 {
+        self.value
+    }
+
+    /// The modulus.
+    pub fn modulus(&self) -> u64 {
+        self.modulus
+    }
+
+    /// Raises this integer to the power `exp`.
+    pub fn pow(self, exp: u64) -> ModInt {
+        self.with_value(pow_mod(self.value, exp, self.modulus))
+    }
+
+    /// The multiplicative inverse of this integer, if it exists.
+    pub fn inverse(self) -> Option<ModInt> {
+        inverse_mod(self.value, self.modulus).map(|value| self.with_value(value))
+    }
+
+    /// The smallest `k` such that `base^k` equals this integer (see [`discrete_log`]).
+    pub fn log(self, base: ModInt) -> Option<u64> {
+        self.assert_same_modulus(base);
+        discrete_log(base.value, self.value, self.modulus)
+    }
+
+    fn with_value(self, value: u64) -> ModInt {
+        ModInt {
+            value,
+            modulus: self.modulus,
+        }
+    }
+
+    fn assert_same_modulus(self, other: ModInt) {
+        assert_eq!(
+            self.modulus, other.modulus,
+            "cannot combine integers with different moduli"
+        );
+    }
+}
+
+impl Add for ModInt {
+    type Output = ModInt;
+
+    fn add(self, rhs: ModInt) -> ModInt {
+        self.assert_same_modulus(rhs);
+        let value = (self.value as u128 + rhs.value as u128) % self.modulus as u128;
+        self.with_value(value as u64)
+    }
+}
+
+impl Sub for ModInt {
+    type Output = ModInt;
+
+    fn sub(self, rhs: ModInt) -> ModInt {
+        self + -rhs
+    }
+}
+
+impl Neg for ModInt {
+    type Output = ModInt;
+
+    fn neg(self) -> ModInt {
+        self.with_value((self.modulus - self.value) % self.modulus)
+    }
+}
+
+impl Mul for ModInt {
+    type Output = ModInt;
+
+    fn mul(self, rhs: ModInt) -> ModInt {
+        self.assert_same_modulus(rhs);
+        self.with_value(mul_mod(self.value, rhs.value, self.modulus))
+    }
+}
+
+impl fmt::Display for ModInt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} (mod {})", self.value, self.modulus)
+    }
+}
+
+/// An affine map `x -> a * x + b (mod m)`. Linear congruential generators and card shuffles are
+/// examples of such maps.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Affine {
+    a: ModInt,
+    b: ModInt,
+}
+
+impl Affine {
+    /// Creates the map `x -> a * x + b (mod modulus)`. Negative values are allowed.
+    pub fn new(a: i128, b: i128, modulus: u64) -> Affine {
+        Affine {
+            a: ModInt::new(a, modulus),
+            b: ModInt::new(b, modulus),
+        }
+    }
+
+    /// The identity map, modulo `modulus`.
+    pub fn identity(modulus: u64) -> Affine {
+        Affine::new(1, 0, modulus)
+    }
+
+    /// The factor `a`.
+    pub fn a(&self) -> u64 {
+        self.a.value
+    }
+
+    /// The offset `b`.
+    pub fn b(&self) -> u64 {
+        self.b.value
+    }
+
+    /// The modulus.
+    pub fn modulus(&self) -> u64 {
+        self.a.modulus
+    }
+
+    /// Applies the map to `x`.
+    pub fn apply(&self, x: u64) -> u64 {
+        (self.a * ModInt::new(x as i128, self.modulus()) + self.b).value
+    }
+
+    /// The map that first applies this map, and then `next`.
+    pub fn then(self, next: Affine) -> Affine {
+        Affine {
+            a: next.a * self.a,
+            b: next.a * self.b + next.b,
+        }
+    }
+
+    /// The map that applies this map `n` times in a row, computed in `O(log n)` steps.
+    pub fn pow(self, mut n: u64) -> Affine {
+        let mut result = Affine::identity(self.modulus());
+        let mut power = self;
+        while n > 0 {
+            if n & 1 == 1 {
+                result = result.then(power);
+            }
+            power = power.then(power);
+            n >>= 1;
+        }
+        result
+    }
+
+    /// The map that undoes this map, if it exists (i.e. if `a` is invertible).
+    pub fn inverse(self) -> Option<Affine> {
+        let a = self.a.inverse()?;
+        Some(Affine {
+            a,
+            b: -(a * self.b),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pow_mod() {
+        assert_eq!(pow_mod(2, 10, 1000), 24);
+        assert_eq!(pow_mod(3, 0, 7), 1);
+        assert_eq!(pow_mod(3, 0, 1), 0);
+        assert_eq!(pow_mod(u64::MAX, 2, u64::MAX - 1), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "the modulus must not be zero")]
+    fn test_pow_mod_zero_modulus() {
+        pow_mod(2, 3, 0);
+    }
+
+    #[test]
+    fn test_inverse_mod() {
+        assert_eq!(inverse_mod(3, 7), Some(5));
+        assert_eq!(inverse_mod(10, 7), Some(5));
+        assert_eq!(inverse_mod(4, 6), None);
+
+        let modulus = u64::MAX - 58; // the largest 64 bit prime
+        let inverse = inverse_mod(123456789, modulus).unwrap();
+        assert_eq!(mul_mod(inverse, 123456789, modulus), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "the modulus must not be zero")]
+    fn test_inverse_mod_zero_modulus() {
+        inverse_mod(3, 0);
+    }
+
+    #[test]
+    fn test_discrete_log() {
+        assert_eq!(discrete_log(7, 5764801, 20201227), Some(8));
+        assert_eq!(discrete_log(7, 17807724, 20201227), Some(11));
+        assert_eq!(discrete_log(2, 1, 11), Some(0));
+        assert_eq!(discrete_log(2, 3, 7), None);
+        assert_eq!(discrete_log(2, 3, 8), None);
+
+        for k in 0..40 {
+            let target = pow_mod(3, k, 101);
+            assert_eq!(discrete_log(3, target, 101), Some(k % 100));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "the modulus must not be zero")]
+    fn test_discrete_log_zero_modulus() {
+        discrete_log(3, 1, 0);
+    }
+
+    #[test]
+    fn test_mod_int() {
+        let a = ModInt::new(-3, 7);
+        let b = ModInt::new(12, 7);
+        assert_eq!(a.value(), 4);
+        assert_eq!((a + b).value(), 2);
+        assert_eq!((a - b).value(), 6);
+        assert_eq!((a * b).value(), 6);
+        assert_eq!((-a).value(), 3);
+        assert_eq!(a.pow(3).value(), 1);
+        assert_eq!(a.inverse().map(|i| (i * a).value()), Some(1));
+        assert_eq!(a.to_string(), "4 (mod 7)");
+
+        // the encryption key from advent of code 2020, day 25
+        let subject = ModInt::new(17807724, 20201227);
+        assert_eq!(subject.pow(8).value(), 14897079);
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot combine integers with different moduli")]
+    fn test_mod_int_different_moduli() {
+        let _ = ModInt::new(1, 7) + ModInt::new(1, 11);
+    }
+
+    #[test]
+    fn test_affine_generator() {
+        // the generators from advent of code 2017, day 15
+        let generator = Affine::new(16807, 0, 2147483647);
+        let values: Vec<u64> = (1..=3).map(|n| generator.pow(n).apply(65)).collect();
+        assert_eq!(values, vec![1092455, 1181022009, 245556042]);
+    }
+
+    #[test]
+    fn test_affine_shuffle() {
+        // the shuffles from advent of code 2019, day 22, with a deck of 10 cards
+        let deal_into_new_stack = Affine::new(-1, -1, 10);
+        let cut = |n: i128| Affine::new(1, -n, 10);
+        let deal_with_increment = |n: i128| Affine::new(n, 0, 10);
+
+        let shuffle = deal_with_increment(7)
+            .then(deal_into_new_stack)
+            .then(deal_into_new_stack);
+        assert_eq!(deck(shuffle), vec![0, 3, 6, 9, 2, 5, 8, 1, 4, 7]);
+
+        let shuffle = cut(6)
+            .then(deal_with_increment(7))
+            .then(deal_into_new_stack);
+        assert_eq!(deck(shuffle), vec![3, 0, 7, 4, 1, 8, 5, 2, 9, 6]);
+
+        let mut repeated = Affine::identity(10);
+        for _ in 0..5 {
+            repeated = repeated.then(shuffle);
+        }
+        assert_eq!(shuffle.pow(5), repeated);
+
+        let inverse = shuffle.inverse().unwrap();
+        assert_eq!(shuffle.then(inverse), Affine::identity(10));
+        assert_eq!(deal_with_increment(2).inverse(), None);
+    }
+
+    /// The order of the cards after applying a shuffle, which maps each card to its position.
+    fn deck(shuffle: Affine) -> Vec<u64> {
+        let mut deck = vec![0; 10];
+        for card in 0..10 {
+            deck[shuffle.apply(card) as usize] = card;
+        }
+        deck
+    }
+}
@@ -0,0 +1,33 @@
+//! Error handling.
+
+use std::error::Error;
+use std::fmt;
+
+/// The reasons why a display could not be read.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum OcrError {
+    /// The display does not contain any pixels that are on.
+    Empty,
+    /// The text in the display has a height which does not match any of the fonts.
+    UnknownFont { height: usize },
+    /// A glyph was found which does not match any character of the font. The column is the
+    /// position of the first column of the glyph in the display, and the bitmap shows the glyph,
+    /// using `#` for pixels that are on and `.` for pixels that are off.
+    UnrecognizedGlyph { column: usize, bitmap: String },
+}
+
+impl fmt::Display for OcrError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OcrError::Empty => write!(f, "The display is empty."),
+            OcrError::UnknownFont { height } => {
+                write!(f, "There is no font with a height of {} pixels.", height)
+            }
+            OcrError::UnrecognizedGlyph { column, bitmap } => {
+                write!(f, "Unrecognized glyph at column {}:\n{}", column, bitmap)
+            }
+        }
+    }
+}
+
+impl Error for OcrError {}
@@ -0,0 +1,380 @@
+//! The large font, which is 10 pixels high. This font is used in puzzles where the text emerges
+//! from a cloud of points, such as [advent of code 2018, day 10](https://adventofcode.com/2018/day/10):
+//!
+//! ```text
+//! .####...#....#..######..######
+//! #....#..#....#..#.......#.....
+//! #.......#....#..#.......#.....
+//! #.......#....#..#.......#.....
+//! #.......######..#####...#####.
+//! #.......#....#..#.......#.....
+//! #.......#....#..#.......#.....
+//! #.......#....#..#.......#.....
+//! #....#..#....#..#.......#.....
+//! .####...#....#..######..#.....
+//! ```
+//!
+//! Not every letter has been seen in this font. Unknown letters are represented by a string of
+//! question marks, which never matches any input.
+
+/// The height of a character.
+pub const CHAR_HEIGHT: usize = 10;
+/// The width of a character. This includes two empty columns on the right-hand side.
+pub const CHAR_WIDTH: usize = 8;
+
+/// The letter A.
+///
+/// ```text
+/// ..##....
+/// .#..#...
+/// #....#..
+/// #....#..
+/// #....#..
+/// ######..
+/// #....#..
+/// #....#..
+/// #....#..
+/// #....#..
+/// ```
+pub const A: &str =
+    "00111111110100010000100001000010000100000100010000001111111100000000000000000000";
+
+/// The letter B.
+///
+/// ```text
+/// #####...
+/// #....#..
+/// #....#..
+/// #....#..
+/// #####...
+/// #....#..
+/// #....#..
+/// #....#..
+/// #....#..
+/// #####...
+/// ```
+pub const B: &str =
+    "11111111111000100001100010000110001000011000100001011101111000000000000000000000";
+
+/// The letter C.
+///
+/// ```text
+/// .####...
+/// #....#..
+/// #.......
+/// #.......
+/// #.......
+/// #.......
+/// #.......
+/// #.......
+/// #....#..
+/// .####...
+/// ```
+pub const C: &str =
+    "01111111101000000001100000000110000000011000000001010000001000000000000000000000";
+
+/// The letter D has not been seen in the large font yet.
+pub const D: &str =
+    "????????????????????????????????????????????????????????????????????????????????"; // not found yet
+
+/// The letter E.
+///
+/// ```text
+/// ######..
+/// #.......
+/// #.......
+/// #.......
+/// #####...
+/// #.......
+/// #.......
+/// #.......
+/// #.......
+/// ######..
+/// ```
+pub const E: &str =
+    "11111111111000100001100010000110001000011000100001100000000100000000000000000000";
+
+/// The letter F.
+///
+/// ```text
+/// ######..
+/// #.......
+/// #.......
+/// #.......
+/// #####...
+/// #.......
+/// #.......
+/// #.......
+/// #.......
+/// #.......
+/// ```
+pub const F: &str =
+    "11111111111000100000100010000010001000001000100000100000000000000000000000000000";
+
+/// The letter G.
+///
+/// ```text
+/// .####...
+/// #....#..
+/// #.......
+/// #.......
+/// #.......
+/// #..###..
+/// #....#..
+/// #....#..
+/// #...##..
+/// .###.#..
+/// ```
+pub const G: &str =
+    "01111111101000000001100000000110000100011000010010010001111100000000000000000000";
+
+/// The letter H.
+///
+/// ```text
+/// #....#..
+/// #....#..
+/// #....#..
+/// #....#..
+/// ######..
+/// #....#..
+/// #....#..
+/// #....#..
+/// #....#..
+/// #....#..
+/// ```
+pub const H: &str =
+    "11111111110000100000000010000000001000000000100000111111111100000000000000000000";
+
+/// The letter I has not been seen in the large font yet.
+pub const I: &str =
+    "????????????????????????????????????????????????????????????????????????????????"; // not found yet
+
+/// The letter J.
+///
+/// ```text
+/// ...###..
+/// ....#...
+/// ....#...
+/// ....#...
+/// ....#...
+/// ....#...
+/// ....#...
+/// #...#...
+/// #...#...
+/// .###....
+/// ```
+pub const J: &str =
+    "00000001100000000001000000000110000000011111111110100000000000000000000000000000";
+
+/// The letter K.
+///
+/// ```text
+/// #....#..
+/// #...#...
+/// #..#....
+/// #.#.....
+/// ##......
+/// ##......
+/// #.#.....
+/// #..#....
+/// #...#...
+/// #....#..
+/// ```
+pub const K: &str =
+    "11111111110000110000000100100000100001000100000010100000000100000000000000000000";
+
+/// The letter L.
+///
+/// ```text
+/// #.......
+/// #.......
+/// #.......
+/// #.......
+/// #.......
+/// #.......
+/// #.......
+/// #.......
+/// #.......
+/// ######..
+/// ```
+pub const L: &str =
+    "11111111110000000001000000000100000000010000000001000000000100000000000000000000";
+
+/// The letter M has not been seen in the large font yet.
+pub const M: &str =
+    "????????????????????????????????????????????????????????????????????????????????"; // not found yet
+
+/// The letter N.
+///
+/// ```text
+/// #....#..
+/// ##...#..
+/// ##...#..
+/// #.#..#..
+/// #.#..#..
+/// #..#.#..
+/// #..#.#..
+/// #...##..
+/// #...##..
+/// #....#..
+/// ```
+pub const N: &str =
+    "11111111110110000000000110000000000110000000000110111111111100000000000000000000";
+
+/// The letter O has not been seen in the large font yet.
+pub const O: &str =
+    "????????????????????????????????????????????????????????????????????????????????"; // not found yet
+
+/// The letter P.
+///
+/// ```text
+/// #####...
+/// #....#..
+/// #....#..
+/// #....#..
+/// #####...
+/// #.......
+/// #.......
+/// #.......
+/// #.......
+/// #.......
+/// ```
+pub const P: &str =
+    "11111111111000100000100010000010001000001000100000011100000000000000000000000000";
+
+/// The letter Q has not been seen in the large font yet.
+pub const Q: &str =
+    "????????????????????????????????????????????????????????????????????????????????"; // not found yet
+
+/// The letter R.
+///
+/// ```text
+/// #####...
+/// #....#..
+/// #....#..
+/// #....#..
+/// #####...
+/// #..#....
+/// #...#...
+/// #...#...
+/// #....#..
+/// #....#..
+/// ```
+pub const R: &str =
+    "11111111111000100000100010000010001100001000101100011100001100000000000000000000";
+
+/// The letter S has not been seen in the large font yet.
+pub const S: &str =
+    "????????????????????????????????????????????????????????????????????????????????"; // not found yet
+
+/// The letter T has not been seen in the large font yet.
+pub const T: &str =
+    "????????????????????????????????????????????????????????????????????????????????"; // not found yet
+
+/// The letter U has not been seen in the large font yet.
+pub const U: &str =
+    "????????????????????????????????????????????????????????????????????????????????"; // not found yet
+
+/// The letter V has not been seen in the large font yet.
+pub const V: &str =
+    "????????????????????????????????????????????????????????????????????????????????"; // not found yet
+
+/// The letter W has not been seen in the large font yet.
+pub const W: &str =
+    "????????????????????????????????????????????????????????????????????????????????"; // not found yet
+
+/// The letter X.
+///
+/// ```text
+/// #....#..
+/// #....#..
+/// .#..#...
+/// .#..#...
+/// ..##....
+/// ..##....
+/// .#..#...
+/// .#..#...
+/// #....#..
+/// #....#..
+/// ```
+pub const X: &str =
+    "11000000110011001100000011000000001100000011001100110000001100000000000000000000";
+
+/// The letter Y has not been seen in the large font yet.
+pub const Y: &str =
+    "????????????????????????????????????????????????????????????????????????????????"; // not found yet
+
+/// The letter Z.
+///
+/// ```text
+/// ######..
+/// .....#..
+/// .....#..
+/// ....#...
+/// ...#....
+/// ..#.....
+/// .#......
+/// #.......
+/// #.......
+/// ######..
+/// ```
+pub const Z: &str =
+    "10000001111000001001100001000110001000011001000001111000000100000000000000000000";
+
+/// An empty space.
+pub const SPACE: &str =
+    "00000000000000000000000000000000000000000000000000000000000000000000000000000000";
+
+/// Tries to match an input to a character.
+///
+/// * `spec` - A string representation of a 8x10 portion of the display, serialized by column.
+///   Use `1` to indicate a pixel that is "on" and `0` to indicate a pixel that is "off".
+///   The string should be exactly 8x10 = 80 characters long.
+pub fn match_char(spec: &str) -> char {
+    match spec {
+        v if v == A => 'A',
+        v if v == B => 'B',
+        v if v == C => 'C',
+        v if v == D => 'D',
+        v if v == E => 'E',
+        v if v == F => 'F',
+        v if v == G => 'G',
+        v if v == H => 'H',
+        v if v == I => 'I',
+        v if v == J => 'J',
+        v if v == K => 'K',
+        v if v == L => 'L',
+        v if v == M => 'M',
+        v if v == N => 'N',
+        v if v == O => 'O',
+        v if v == P => 'P',
+        v if v == Q => 'Q',
+        v if v == R => 'R',
+        v if v == S => 'S',
+        v if v == T => 'T',
+        v if v == U => 'U',
+        v if v == V => 'V',
+        v if v == W => 'W',
+        v if v == X => 'X',
+        v if v == Y => 'Y',
+        v if v == Z => 'Z',
+        v if v == SPACE => ' ',
+        _ => '?',
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_char_length() {
+        let expected = CHAR_WIDTH * CHAR_HEIGHT;
+
+        for spec in [
+            A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, SPACE,
+        ] {
+            assert_eq!(spec.len(), expected);
+        }
+    }
+}
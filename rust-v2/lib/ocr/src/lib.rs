@@ -11,8 +11,15 @@
 //!
 //! This output represents text (this particular example reads: "HELLO").
 //! This library provides a helper method to convert this type of output to a string.
+//!
+//! There are two fonts: the small font, which is 6 pixels high and is defined in this module, and
+//! the [`large`] font, which is 10 pixels high. The [`ocr`] function detects which font is used.
+
+pub mod error;
+pub mod large;
+pub mod pixels;
+pub mod render;
 
-#[cfg(test)]
 /// The height of a character.
 pub const CHAR_HEIGHT: usize = 6;
 /// The width of a character. This includes an empty column on the right-hand side.
@@ -289,8 +296,8 @@ pub const SPACE: &str = "000000000000000000000000000000";
 /// Tries to match an input to a character.
 ///
 /// * `spec` - A string representation of a 5x6 portion of the display, serialized by column.
-///            Use `1` to indicate a pixel that is "on" and `0` to indicate a pixel that is "off".
-///            The string should be exactly 5x6 = 30 characters long.
+///   Use `1` to indicate a pixel that is "on" and `0` to indicate a pixel that is "off".
+///   The string should be exactly 5x6 = 30 characters long.
 pub fn match_char(spec: &str) -> char {
     match spec {
         v if v == A => 'A',
@@ -324,31 +331,148 @@ pub fn match_char(spec: &str) -> char {
     }
 }
 
-/// Tries to interpret the output as text.
+/// The fonts that can be recognized.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Font {
+    /// The small font, in which characters are 6 pixels high.
+    Small,
+    /// The [`large`] font, in which characters are 10 pixels high.
+    Large,
+}
+
+impl Font {
+    /// Detects the font of a display, based on the number of rows it has.
+    pub fn detect(display: &str) -> Option<Font> {
+        match display.split('\n').filter(|l| !l.is_empty()).count() {
+            CHAR_HEIGHT => Some(Font::Small),
+            large::CHAR_HEIGHT => Some(Font::Large),
+            _ => None,
+        }
+    }
+
+    /// The height of a character in this font.
+    pub fn char_height(&self) -> usize {
+        match self {
+            Font::Small => CHAR_HEIGHT,
+            Font::Large => large::CHAR_HEIGHT,
+        }
+    }
+
+    /// The width of a character in this font, including the empty columns on the right-hand side.
+    pub fn char_width(&self) -> usize {
+        match self {
+            Font::Small => CHAR_WIDTH,
+            Font::Large => large::CHAR_WIDTH,
+        }
+    }
+
+    /// The letters that are known in this font, together with their specs (see [`match_char`]).
+    pub fn glyphs(&self) -> Vec<(char, &'static str)> {
+        let specs = match self {
+            Font::Small => [
+                A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z,
+            ],
+            Font::Large => [
+                large::A,
+                large::B,
+                large::C,
+                large::D,
+                large::E,
+                large::F,
+                large::G,
+                large::H,
+                large::I,
+                large::J,
+                large::K,
+                large::L,
+                large::M,
+                large::N,
+                large::O,
+                large::P,
+                large::Q,
+                large::R,
+                large::S,
+                large::T,
+                large::U,
+                large::V,
+                large::W,
+                large::X,
+                large::Y,
+                large::Z,
+            ],
+        };
+
+        ('A'..='Z')
+            .zip(specs)
+            .filter(|(_, spec)| !spec.contains('?'))
+            .collect()
+    }
+
+    /// Tries to match an input to a character in this font. See [`match_char`] and
+    /// [`large::match_char`].
+    pub fn match_char(&self, spec: &str) -> char {
+        match self {
+            Font::Small => match_char(spec),
+            Font::Large => large::match_char(spec),
+        }
+    }
+}
+
+/// Tries to interpret the output as text. The font is detected automatically (see
+/// [`Font::detect`]). If the font cannot be detected, the small font is assumed.
 ///
 /// Assumptions:
-/// - Each character has a width of 5 pixels and a height of 6 pixels.
 /// - The display only contains a single line of text.
 /// - Each row in the display has the same width.
+///
+/// ```
+/// use rdcl_aoc_ocr::ocr;
+///
+/// let display = "\
+///     .####...#....#..######..######\n\
+///     #....#..#....#..#.......#.....\n\
+///     #.......#....#..#.......#.....\n\
+///     #.......#....#..#.......#.....\n\
+///     #.......######..#####...#####.\n\
+///     #.......#....#..#.......#.....\n\
+///     #.......#....#..#.......#.....\n\
+///     #.......#....#..#.......#.....\n\
+///     #....#..#....#..#.......#.....\n\
+///     .####...#....#..######..#.....\n\
+/// ";
+///
+/// assert_eq!(ocr(display), "CHEF");
+/// ```
 pub fn ocr(display: &str) -> String {
+    ocr_with_font(display, Font::detect(display).unwrap_or(Font::Small))
+}
+
+/// Tries to interpret the output as text, using the specified font.
+///
+/// Assumptions:
+/// - Each character has the width and height of the specified font. The empty columns to the
+///   right of the last character may be omitted.
+/// - The display only contains a single line of text.
+/// - Each row in the display has the same width.
+pub fn ocr_with_font(display: &str, font: Font) -> String {
     let lines = display
         .split('\n')
         .filter(|l| !l.is_empty())
         .map(|l| l.chars().map(|c| c == '#').collect::<Vec<bool>>())
         .collect::<Vec<Vec<bool>>>();
 
+    let width = font.char_width();
+    let columns = lines.first().map_or(0, |line| line.len());
     let mut current = String::new();
     let mut text = String::new();
 
-    for i in 0..lines[0].len() {
+    for i in 0..columns.div_ceil(width) * width {
         for line in &lines {
-            current.push(if line[i] { '1' } else { '0' });
+            current.push(if line.get(i) == Some(&true) { '1' } else { '0' });
         }
 
-        if i % CHAR_WIDTH == CHAR_WIDTH - 1 {
-            if !current.is_empty() {
-                text.push(match_char(&current));
-            }
+        if i % width == width - 1 {
+            text.push(font.match_char(&current));
             current.clear();
         }
     }
@@ -419,4 +543,45 @@ mod tests {
 
         assert_eq!(ocr(input), "?OP?RSTU???YZ".to_string());
     }
+
+    #[test]
+    fn test_detect_font() {
+        assert_eq!(Font::detect(&"#\n".repeat(6)), Some(Font::Small));
+        assert_eq!(Font::detect(&"#\n".repeat(10)), Some(Font::Large));
+        assert_eq!(Font::detect(&"#\n".repeat(8)), None);
+    }
+
+    #[test]
+    fn test_ocr_large() {
+        // the empty columns after the last character are omitted
+        let input = "\
+          ..##....#####....####...######..######...####...#....#.....###..#....#..#.......\n\
+          .#..#...#....#..#....#..#.......#.......#....#..#....#......#...#...#...#.......\n\
+          #....#..#....#..#.......#.......#.......#.......#....#......#...#..#....#.......\n\
+          #....#..#....#..#.......#.......#.......#.......#....#......#...#.#.....#.......\n\
+          #....#..#####...#.......#####...#####...#.......######......#...##......#.......\n\
+          ######..#....#..#.......#.......#.......#..###..#....#......#...##......#.......\n\
+          #....#..#....#..#.......#.......#.......#....#..#....#......#...#.#.....#.......\n\
+          #....#..#....#..#.......#.......#.......#....#..#....#..#...#...#..#....#.......\n\
+          #....#..#....#..#....#..#.......#.......#...##..#....#..#...#...#...#...#.......\n\
+          #....#..#####....####...######..#........###.#..#....#...###....#....#..######..\n\
+        ";
+
+        assert_eq!(ocr(input), "ABCEFGHJKL".to_string());
+
+        let input = "\
+          #....#..#####...#####...#....#..######........\n\
+          ##...#..#....#..#....#..#....#.......#........\n\
+          ##...#..#....#..#....#...#..#........#........\n\
+          #.#..#..#....#..#....#...#..#.......#.........\n\
+          #.#..#..#####...#####.....##.......#..........\n\
+          #..#.#..#.......#..#......##......#...........\n\
+          #..#.#..#.......#...#....#..#....#............\n\
+          #...##..#.......#...#....#..#...#.............\n\
+          #...##..#.......#....#..#....#..#.............\n\
+          #....#..#.......#....#..#....#..######........\n\
+        ";
+
+        assert_eq!(ocr(input), "NPRXZ ".to_string());
+    }
 }
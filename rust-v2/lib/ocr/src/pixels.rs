@@ -0,0 +1,426 @@
+//! Reading text from arbitrary pixel sources.
+//!
+//! Unlike [`ocr`](crate::ocr), which expects a string in which every character has exactly the
+//! width of the font, [`Pixels::read`] first crops the display to the pixels that are on, and
+//! then splits it into glyphs on the columns that are completely off. This makes it possible to
+//! read text that comes from a cloud of points, or from a display that has some margin. If a
+//! glyph cannot be recognized, the error contains its bitmap.
+//!
+//! # Usage
+//!
+//! ```
+//! use rdcl_aoc_ocr::pixels::Pixels;
+//!
+//! let display = "
+//!     ..........................
+//!     ..█..█.████.█....█.....██.
+//!     ..█..█.█....█....█....█..█
+//!     ..████.███..█....█....█..█
+//!     ..█..█.█....█....█....█..█
+//!     ..█..█.█....█....█....█..█
+//!     ..█..█.████.████.████..██.
+//! ";
+//!
+//! assert_eq!(Pixels::parse(display).read().unwrap(), "HELLO");
+//! ```
+
+use std::collections::HashSet;
+
+use crate::error::OcrError;
+use crate::Font;
+
+/// The characters that represent a pixel that is on. Any other character represents a pixel that
+/// is off.
+pub const ON: [char; 3] = ['#', '█', 'o'];
+
+/// A rectangular display of pixels, which are either on or off.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Pixels {
+    width: usize,
+    rows: Vec<Vec<bool>>,
+}
+
+impl Pixels {
+    /// Parses a display in which every line is a row of pixels (see [`ON`]). Blank lines before
+    /// and after the display are ignored.
+    ///
+    /// If the display contains any character other than whitespace for pixels that are off (such
+    /// as `.`), the indentation that all lines have in common is ignored, as is whitespace at the
+    /// end of every line. Otherwise, whitespace is read as pixels that are off, including any
+    /// indentation.
+    pub fn parse(display: &str) -> Pixels {
+        let lines: Vec<&str> = display.lines().collect();
+        let is_blank = |line: &&str| line.trim().is_empty();
+        let first = lines.iter().position(|line| !is_blank(line));
+        let last = lines.iter().rposition(|line| !is_blank(line));
+        let lines = match (first, last) {
+            (Some(first), Some(last)) => &lines[first..=last],
+            _ => &[][..],
+        };
+
+        // whitespace only draws pixels if there is no other character for pixels that are off
+        let spaces_are_off = !lines
+            .iter()
+            .flat_map(|line| line.chars())
+            .any(|ch| !ch.is_whitespace() && !ON.contains(&ch));
+        let indentation = if spaces_are_off {
+            0
+        } else {
+            lines
+                .iter()
+                .filter(|line| !is_blank(line))
+                .map(|line| line.chars().take_while(|ch| ch.is_whitespace()).count())
+                .min()
+                .unwrap_or(0)
+        };
+
+        let rows = lines
+            .iter()
+            .map(|&line| {
+                if spaces_are_off {
+                    line
+                } else {
+                    line.trim_end()
+                }
+            })
+            .map(|line| {
+                line.chars()
+                    .skip(indentation)
+                    .map(|ch| ON.contains(&ch))
+                    .collect()
+            })
+            .collect();
+
+        Pixels::from_rows(rows)
+    }
+
+    /// Creates a display of `width` by `height` pixels, where `is_on(x, y)` tells whether the
+    /// pixel in column `x` and row `y` is on.
+    pub fn from_fn<F>(width: usize, height: usize, is_on: F) -> Pixels
+    where
+        F: Fn(usize, usize) -> bool,
+    {
+        let rows = (0..height)
+            .map(|y| (0..width).map(|x| is_on(x, y)).collect())
+            .collect();
+
+        Pixels { width, rows }
+    }
+
+    /// Creates a display in which exactly the given points (as `(x, y)`) are on. The points may
+    /// have any offset, including a negative one.
+    pub fn from_points<I>(points: I) -> Pixels
+    where
+        I: IntoIterator<Item = (i64, i64)>,
+    {
+        let points: HashSet<(i64, i64)> = points.into_iter().collect();
+        let Some(min_x) = points.iter().map(|&(x, _)| x).min() else {
+            return Pixels::from_rows(vec![]);
+        };
+        let min_y = points.iter().map(|&(_, y)| y).min().unwrap();
+        let max_x = points.iter().map(|&(x, _)| x).max().unwrap();
+        let max_y = points.iter().map(|&(_, y)| y).max().unwrap();
+
+        Pixels::from_fn(
+            (max_x - min_x + 1) as usize,
+            (max_y - min_y + 1) as usize,
+            |x, y| points.contains(&(min_x + x as i64, min_y + y as i64)),
+        )
+    }
+
+    /// Pads the rows to the same width.
+    fn from_rows(mut rows: Vec<Vec<bool>>) -> Pixels {
+        let width = rows.iter().map(|row| row.len()).max().unwrap_or(0);
+        for row in &mut rows {
+            row.resize(width, false);
+        }
+
+        Pixels { width, rows }
+    }
+
+    /// The width of the display.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// The height of the display.
+    pub fn height(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Checks whether the pixel in column `x` and row `y` is on. Pixels outside of the display
+    /// are off.
+    pub fn is_on(&self, x: usize, y: usize) -> bool {
+        self.rows
+            .get(y)
+            .and_then(|row| row.get(x))
+            .copied()
+            .unwrap_or(false)
+    }
+
+    /// Tries to interpret the display as a single line of text. The font is detected from the
+    /// height of the text. Runs of empty columns that are at least as wide as a character are
+    /// read as spaces.
+    pub fn read(&self) -> Result<String, OcrError> {
+        let is_column_empty = |x: usize| (0..self.height()).all(|y| !self.is_on(x, y));
+        let is_row_empty = |y: usize| (0..self.width).all(|x| !self.is_on(x, y));
+
+        let Some(left) = (0..self.width).find(|&x| !is_column_empty(x)) else {
+            return Err(OcrError::Empty);
+        };
+        let right = (0..self.width).rfind(|&x| !is_column_empty(x)).unwrap();
+        let top = (0..self.height()).find(|&y| !is_row_empty(y)).unwrap();
+        let bottom = (0..self.height()).rfind(|&y| !is_row_empty(y)).unwrap();
+
+        let height = bottom - top + 1;
+        let font = match height {
+            crate::CHAR_HEIGHT => Font::Small,
+            crate::large::CHAR_HEIGHT => Font::Large,
+            _ => return Err(OcrError::UnknownFont { height }),
+        };
+
+        // every column of the text, serialized in the same way as the specs of the font
+        let columns: Vec<String> = (left..=right)
+            .map(|x| {
+                (top..=bottom)
+                    .map(|y| if self.is_on(x, y) { '1' } else { '0' })
+                    .collect()
+            })
+            .collect();
+        let glyphs: Vec<(char, Vec<&str>)> = font
+            .glyphs()
+            .into_iter()
+            .map(|(ch, spec)| (ch, trimmed_columns(spec, height)))
+            .collect();
+        let max_glyph_width = glyphs.iter().map(|(_, g)| g.len()).max().unwrap_or(0);
+        let empty_column = "0".repeat(height);
+
+        let mut text = String::new();
+        let mut x = 0;
+        while x < columns.len() {
+            if columns[x] == empty_column {
+                let gap = columns[x..]
+                    .iter()
+                    .take_while(|&column| *column == empty_column)
+                    .count();
+                for _ in 0..gap / font.char_width() {
+                    text.push(' ');
+                }
+                x += gap;
+                continue;
+            }
+
+            // glyphs are usually separated by an empty column, but some glyphs (such as the Y in
+            // the small font) touch the next one, so try the widest glyphs first
+            let run = columns[x..]
+                .iter()
+                .take_while(|&column| *column != empty_column)
+                .count();
+            let matched = glyphs
+                .iter()
+                .filter(|(_, g)| g.len() <= run.min(max_glyph_width))
+                .filter(|(_, g)| g.iter().zip(&columns[x..]).all(|(a, b)| a == b))
+                .max_by_key(|(_, g)| g.len());
+
+            match matched {
+                Some((ch, g)) => {
+                    text.push(*ch);
+                    x += g.len();
+                }
+                None => {
+                    return Err(OcrError::UnrecognizedGlyph {
+                        column: left + x,
+                        bitmap: render_columns(&columns[x..x + run]),
+                    })
+                }
+            }
+        }
+
+        Ok(text)
+    }
+}
+
+#[cfg(feature = "grid")]
+impl From<&grid::Grid<bool>> for Pixels {
+    /// Creates a display from a grid, where `true` means that a pixel is on.
+    fn from(grid: &grid::Grid<bool>) -> Self {
+        Pixels::from_fn(grid.cols(), grid.rows(), |x, y| grid[(y, x)])
+    }
+}
+
+/// Splits a spec into its columns, and removes the empty columns on either side.
+fn trimmed_columns(spec: &str, height: usize) -> Vec<&str> {
+    let columns: Vec<&str> = (0..spec.len() / height)
+        .map(|x| &spec[x * height..(x + 1) * height])
+        .collect();
+    let left = columns.iter().take_while(|c| !c.contains('1')).count();
+    let right = columns
+        .iter()
+        .rev()
+        .take_while(|c| !c.contains('1'))
+        .count();
+
+    columns[left..columns.len() - right].to_vec()
+}
+
+/// Renders columns of `0`s and `1`s as rows of `.`s and `#`s.
+fn render_columns(columns: &[String]) -> String {
+    let height = columns.first().map_or(0, |column| column.len());
+    (0..height)
+        .map(|y| {
+            columns
+                .iter()
+                .map(|column| match column.as_bytes()[y] {
+                    b'1' => '#',
+                    _ => '.',
+                })
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_with_margin_and_spaces() {
+        let display = "
+            ...........................................
+            .###..####.#...#..........#..#..##..###....
+            .#..#.#....#...#..........#..#.#..#.#..#...
+            .###..###...#.#...........#..#.#..#.###....
+            .#..#.#......#............#..#.#..#.#..#...
+            .#..#.#......#............#..#.#..#.#..#...
+            .###..####...#.............##...##..###....
+            ...........................................
+        ";
+
+        assert_eq!(Pixels::parse(display).read(), Ok("BEY  UOB".to_string()));
+    }
+
+    #[test]
+    fn test_read_with_spaces_for_off() {
+        let display = crate::render::render("AB")
+            .replace('.', " ")
+            .replace('#', "█");
+        assert!(display.starts_with(' '));
+
+        let pixels = Pixels::parse(&display);
+        assert_eq!(pixels.width(), 10);
+        assert_eq!(pixels.read(), Ok("AB".to_string()));
+
+        // the indentation is read as pixels that are off
+        let indented: String = display
+            .lines()
+            .map(|line| format!("    {}\n", line))
+            .collect();
+        assert_eq!(Pixels::parse(&indented).width(), 14);
+        assert_eq!(Pixels::parse(&indented).read(), Ok("AB".to_string()));
+    }
+
+    #[test]
+    fn test_read_with_spaces_for_off_and_empty_first_column() {
+        let dots = crate::render::render("T");
+        let spaces = dots.replace('.', " ").replace('#', "█");
+        assert!(spaces.lines().all(|line| line.starts_with(' ')));
+
+        let pixels = Pixels::parse(&spaces);
+        assert_eq!(pixels, Pixels::parse(&dots));
+        assert_eq!(pixels.width(), 5);
+        assert!(!pixels.is_on(0, 0));
+        assert!(pixels.is_on(1, 0));
+        assert_eq!(pixels.read(), Ok("T".to_string()));
+    }
+
+    #[test]
+    fn test_parse_indented() {
+        let display = "
+            .#..
+            .#..
+        ";
+        let pixels = Pixels::parse(display);
+        assert_eq!((pixels.width(), pixels.height()), (4, 2));
+        assert!(!pixels.is_on(0, 0));
+        assert!(pixels.is_on(1, 1));
+    }
+
+    #[test]
+    fn test_read_touching_glyphs() {
+        // the Y touches the Z
+        let display = "
+            #...#####
+            #...#...#
+            .#.#...#.
+            ..#...#..
+            ..#..#...
+            ..#..####
+        ";
+
+        assert_eq!(Pixels::parse(display).read(), Ok("YZ".to_string()));
+    }
+
+    #[test]
+    fn test_read_from_points() {
+        // the letter I, far away from the origin
+        let points = [(0, 0), (1, 0), (2, 0), (1, 1), (1, 2), (1, 3), (1, 4)]
+            .into_iter()
+            .chain([(0, 5), (1, 5), (2, 5)])
+            .map(|(x, y)| (x - 100, y + 100));
+
+        assert_eq!(Pixels::from_points(points).read(), Ok("I".to_string()));
+    }
+
+    #[test]
+    fn test_read_from_fn() {
+        let pixels = Pixels::from_fn(10, 10, |x, y| x == 2 || (y == 9 && (2..8).contains(&x)));
+        assert_eq!(pixels.read(), Ok("L".to_string()));
+    }
+
+    #[cfg(feature = "grid")]
+    #[test]
+    fn test_read_from_grid() {
+        let mut grid = grid::Grid::new(6, 4);
+        for (row, col) in [
+            (0, 0),
+            (1, 0),
+            (2, 0),
+            (3, 0),
+            (4, 0),
+            (5, 0),
+            (5, 1),
+            (5, 2),
+            (5, 3),
+        ] {
+            grid[(row, col)] = true;
+        }
+        assert_eq!(Pixels::from(&grid).read(), Ok("L".to_string()));
+    }
+
+    #[test]
+    fn test_read_errors() {
+        assert_eq!(Pixels::parse("").read(), Err(OcrError::Empty));
+        assert_eq!(Pixels::parse("...\n...").read(), Err(OcrError::Empty));
+        assert_eq!(
+            Pixels::parse("#\n#\n#").read(),
+            Err(OcrError::UnknownFont { height: 3 })
+        );
+
+        let display = "
+            ..#...#.####.
+            ..##.##.#..#.
+            ..#.#.#.####.
+            ..#...#.#..#.
+            ..#...#.#..#.
+            ..#...#.#..#.
+        ";
+        assert_eq!(
+            Pixels::parse(display).read(),
+            Err(OcrError::UnrecognizedGlyph {
+                column: 2,
+                bitmap: "#...#\n##.##\n#.#.#\n#...#\n#...#\n#...#".to_string(),
+            })
+        );
+    }
+}
@@ -0,0 +1,136 @@
+//! The reverse of OCR: renders text in one of the fonts. This is useful to generate displays for
+//! tests, without needing an actual puzzle input.
+//!
+//! # Usage
+//!
+//! ```
+//! use rdcl_aoc_ocr::ocr;
+//! use rdcl_aoc_ocr::render::render;
+//!
+//! let display = render("CODE");
+//! assert_eq!(
+//!     display,
+//!     "\
+//!     .##...##..###..####.\n\
+//!     #..#.#..#.#..#.#....\n\
+//!     #....#..#.#..#.###..\n\
+//!     #....#..#.#..#.#....\n\
+//!     #..#.#..#.#..#.#....\n\
+//!     .##...##..###..####.\n\
+//!     "
+//! );
+//! assert_eq!(ocr(&display), "CODE");
+//! ```
+
+use crate::{large, Font, SPACE};
+
+/// Renders text in the small font, using `#` for pixels that are on and `.` for pixels that are
+/// off. Every row ends with a newline, and every character has the full width of the font, so the
+/// result can be read with [`ocr`](crate::ocr).
+///
+/// # Panics
+///
+/// Panics if the text contains a character which is not known in the font.
+pub fn render(text: &str) -> String {
+    render_with_font(text, Font::Small)
+}
+
+/// Renders text in the specified font. See [`render`].
+///
+/// # Panics
+///
+/// Panics if the text contains a character which is not known in the font.
+pub fn render_with_font(text: &str, font: Font) -> String {
+    let mut display = String::new();
+    for row in render_rows(text, font) {
+        display.extend(row.into_iter().map(|on| if on { '#' } else { '.' }));
+        display.push('\n');
+    }
+    display
+}
+
+/// Renders text in the specified font to a grid, where `true` means that a pixel is on.
+///
+/// # Panics
+///
+/// Panics if the text contains a character which is not known in the font.
+#[cfg(feature = "grid")]
+pub fn render_to_grid(text: &str, font: Font) -> grid::Grid<bool> {
+    let rows = render_rows(text, font);
+    let cols = rows.first().map_or(0, |row| row.len());
+    grid::Grid::from_vec(rows.into_iter().flatten().collect(), cols)
+}
+
+/// Renders text as rows of pixels.
+fn render_rows(text: &str, font: Font) -> Vec<Vec<bool>> {
+    let glyphs = font.glyphs();
+    let height = font.char_height();
+    let mut rows = vec![vec![]; height];
+
+    for ch in text.chars() {
+        let spec = match ch {
+            ' ' if font == Font::Large => large::SPACE,
+            ' ' => SPACE,
+            _ => match glyphs.iter().find(|(c, _)| *c == ch) {
+                Some((_, spec)) => spec,
+                None => panic!(
+                    "The character '{}' is not known in the {:?} font.",
+                    ch, font
+                ),
+            },
+        };
+
+        // specs are serialized by column
+        for (i, pixel) in spec.chars().enumerate() {
+            rows[i % height].push(pixel == '1');
+        }
+    }
+
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pixels::Pixels;
+    use crate::{ocr, ocr_with_font};
+
+    #[test]
+    fn test_round_trip_small() {
+        let text = "ABCEFGHIJKLOPRSTUYZ";
+        assert_eq!(ocr(&render(text)), text);
+        assert_eq!(Pixels::parse(&render(text)).read(), Ok(text.to_string()));
+
+        let text = "FOO BAR";
+        assert_eq!(ocr_with_font(&render(text), Font::Small), text);
+        assert_eq!(Pixels::parse(&render(text)).read(), Ok(text.to_string()));
+    }
+
+    #[test]
+    fn test_round_trip_large() {
+        let text = "ABCEFGHJKLNPRXZ";
+        let display = render_with_font(text, Font::Large);
+        assert_eq!(display.lines().count(), 10);
+        assert_eq!(ocr(&display), text);
+        assert_eq!(Pixels::parse(&display).read(), Ok(text.to_string()));
+
+        let text = "FLAX BREAK";
+        let display = render_with_font(text, Font::Large);
+        assert_eq!(ocr(&display), text);
+        assert_eq!(Pixels::parse(&display).read(), Ok(text.to_string()));
+    }
+
+    #[test]
+    #[should_panic(expected = "The character 'M' is not known in the Small font.")]
+    fn test_render_unknown_character() {
+        render("MOO");
+    }
+
+    #[cfg(feature = "grid")]
+    #[test]
+    fn test_render_to_grid() {
+        let grid = render_to_grid("LO", Font::Small);
+        assert_eq!(grid.size(), (6, 10));
+        assert_eq!(Pixels::from(&grid).read(), Ok("LO".to_string()));
+    }
+}
@@ -0,0 +1,456 @@
+//! A generic [branch and bound](https://en.wikipedia.org/wiki/Branch_and_bound) search, for
+//! puzzles that ask for the best possible outcome rather than for a path.
+//!
+//! The search space is described by implementing [`BranchAndBound`]: a state has children, an
+//! optimistic estimate of the best score that can still be reached from it, and (optionally) a
+//! score of its own. The search explores the most promising states first, and discards every state
+//! whose upper bound cannot beat the best score that was found so far.
+//!
+//! # Usage
+//!
+//! ```
+//! use rdcl_aoc_pathfinding::branch_and_bound::BranchAndBound;
+//!
+//! /// Pick items with a total weight of at most 10, so that their value is maximal.
+//! struct Knapsack {
+//!     items: Vec<(u32, u32)>, // (weight, value)
+//! }
+//!
+//! #[derive(Clone, Hash, Eq, PartialEq)]
+//! struct State {
+//!     next: usize,
+//!     weight: u32,
+//!     value: u32,
+//! }
+//!
+//! impl BranchAndBound for Knapsack {
+//!     type State = State;
+//!     type Score = u32;
+//!     type Key = ();
+//!
+//!     fn children(&self, state: &State) -> Vec<State> {
+//!         let Some(&(weight, value)) = self.items.get(state.next) else {
+//!             return vec![];
+//!         };
+//!         let skip = State { next: state.next + 1, ..*state };
+//!         if state.weight + weight > 10 {
+//!             return vec![skip];
+//!         }
+//!         let take = State {
+//!             next: state.next + 1,
+//!             weight: state.weight + weight,
+//!             value: state.value + value,
+//!         };
+//!         vec![take, skip]
+//!     }
+//!
+//!     fn upper_bound(&self, state: &State) -> u32 {
+//!         state.value + self.items[state.next..].iter().map(|(_, v)| v).sum::<u32>()
+//!     }
+//!
+//!     fn score(&self, state: &State) -> Option<u32> {
+//!         Some(state.value)
+//!     }
+//! }
+//!
+//! let knapsack = Knapsack { items: vec![(5, 10), (4, 40), (6, 30), (3, 50)] };
+//! let start = State { next: 0, weight: 0, value: 0 };
+//! assert_eq!(knapsack.maximise([start.clone()]).unwrap().score, 90);
+//! assert_eq!(knapsack.maximise_parallel([start]).unwrap().score, 90);
+//! ```
+
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::sync::Mutex;
+use std::thread;
+
+/// The best state that was found by a branch and bound search.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Solution<S, V> {
+    /// The score of the best state.
+    pub score: V,
+    /// The best state.
+    pub state: S,
+    /// The number of states that were explored (i.e. of which the children were examined) during
+    /// the search.
+    pub explored: usize,
+}
+
+/// Describes a search space in which the state with the highest score must be found.
+pub trait BranchAndBound {
+    /// The representation of a state.
+    type State: Clone;
+    /// The type of the scores.
+    type Score: Copy + Ord;
+    /// The key that is used for memoisation and dominance pruning, see [`key`](Self::key). Use
+    /// `()` if neither is needed.
+    type Key: Hash + Eq;
+
+    /// Returns the states that can be reached directly from `state`.
+    fn children(&self, state: &Self::State) -> Vec<Self::State>;
+
+    /// An optimistic estimate of the best score of any state that can be reached from `state`
+    /// (including `state` itself). Should never be lower than the actual best score, otherwise
+    /// the best state might be missed.
+    fn upper_bound(&self, state: &Self::State) -> Self::Score;
+
+    /// The score of `state`, or `None` if `state` is not a valid solution by itself.
+    fn score(&self, state: &Self::State) -> Option<Self::Score>;
+
+    /// A key that groups states which can be compared using [`dominates`](Self::dominates). A
+    /// state is only explored if no state with the same key that was explored earlier dominates
+    /// it. Returns `None` by default, which disables memoisation and dominance pruning.
+    ///
+    /// If the key describes the entire state, then states with equal keys are identical, so this
+    /// acts as plain memoisation.
+    fn key(&self, _state: &Self::State) -> Option<Self::Key> {
+        None
+    }
+
+    /// Returns `true` if every score that can be reached from `b` can also be reached from `a`, in
+    /// which case `b` does not need to be explored anymore. Only states with the same
+    /// [`key`](Self::key) are compared. Returns `true` by default, i.e. states with the same key
+    /// are considered to be interchangeable.
+    fn dominates(&self, _a: &Self::State, _b: &Self::State) -> bool {
+        true
+    }
+
+    /// Finds the state with the highest score that can be reached from any of the `starts`.
+    /// Returns `None` if no valid solution exists.
+    fn maximise<I>(&self, starts: I) -> Option<Solution<Self::State, Self::Score>>
+    where
+        I: IntoIterator<Item = Self::State>,
+    {
+        let mut search = Search::new(self);
+        search.run(starts.into_iter().collect(), || None);
+        search.solution()
+    }
+
+    /// Like [`maximise`](Self::maximise), but explores the search space on all available
+    /// threads. The states close to the starts are divided between the threads, which share the
+    /// best score that was found so far. Memoisation and dominance pruning only take place within
+    /// a thread.
+    fn maximise_parallel<I>(&self, starts: I) -> Option<Solution<Self::State, Self::Score>>
+    where
+        Self: Sync,
+        Self::State: Send,
+        Self::Score: Send,
+        I: IntoIterator<Item = Self::State>,
+    {
+        let threads = thread::available_parallelism().map_or(1, |n| n.get());
+
+        // expand the states closest to the starts until there is enough work for every thread
+        let mut search = Search::new(self);
+        let mut tasks: Vec<Self::State> = starts.into_iter().collect();
+        while !tasks.is_empty() && tasks.len() < threads * 4 {
+            let mut next = vec![];
+            for state in tasks {
+                if search.visit(self.upper_bound(&state), &state) {
+                    next.extend(self.children(&state));
+                }
+            }
+            tasks = next;
+        }
+
+        let shared = Mutex::new(search.best.take());
+        let explored = Mutex::new(search.explored);
+        let mut stack = vec![];
+        search.push_all(&mut stack, tasks);
+        let tasks = Mutex::new(stack);
+        thread::scope(|scope| {
+            for _ in 0..threads {
+                scope.spawn(|| {
+                    let mut search = Search::new(self);
+                    loop {
+                        let Some((_, task)) = tasks.lock().unwrap().pop() else {
+                            break;
+                        };
+                        search.best = shared.lock().unwrap().clone();
+                        search.run(vec![task], || shared.lock().unwrap().clone());
+                        let mut shared = shared.lock().unwrap();
+                        if search.is_better(shared.as_ref()) {
+                            *shared = search.best.clone();
+                        }
+                    }
+                    *explored.lock().unwrap() += search.explored;
+                });
+            }
+        });
+
+        let explored = explored.into_inner().unwrap();
+        shared.into_inner().unwrap().map(|(score, state)| Solution {
+            score,
+            state,
+            explored,
+        })
+    }
+}
+
+/// The number of states that a thread processes before it checks whether another thread found a
+/// better solution.
+const SYNC_INTERVAL: usize = 1024;
+
+type Best<S, V> = Option<(V, S)>;
+
+/// The state of a (single threaded) branch and bound search.
+struct Search<'a, B: BranchAndBound + ?Sized> {
+    problem: &'a B,
+    best: Best<B::State, B::Score>,
+    seen: HashMap<B::Key, Vec<B::State>>,
+    explored: usize,
+}
+
+impl<'a, B: BranchAndBound + ?Sized> Search<'a, B> {
+    fn new(problem: &'a B) -> Search<'a, B> {
+        Search {
+            problem,
+            best: None,
+            seen: HashMap::new(),
+            explored: 0,
+        }
+    }
+
+    fn solution(self) -> Option<Solution<B::State, B::Score>> {
+        let explored = self.explored;
+        self.best.map(|(score, state)| Solution {
+            score,
+            state,
+            explored,
+        })
+    }
+
+    fn is_better(&self, other: Option<&(B::Score, B::State)>) -> bool {
+        match (&self.best, other) {
+            (Some((score, _)), Some((other, _))) => score > other,
+            (best, _) => best.is_some(),
+        }
+    }
+
+    /// Pairs states with their upper bound and pushes them onto `stack`, so that the most
+    /// promising state ends up on top.
+    fn push_all(&self, stack: &mut Vec<(B::Score, B::State)>, states: Vec<B::State>) {
+        let start = stack.len();
+        stack.extend(
+            states
+                .into_iter()
+                .map(|state| (self.problem.upper_bound(&state), state)),
+        );
+        stack[start..].sort_by_key(|(bound, _)| *bound);
+    }
+
+    /// Processes a single state: updates the best solution, and decides whether the children of
+    /// this state need to be explored.
+    fn visit(&mut self, bound: B::Score, state: &B::State) -> bool {
+        if self.best.as_ref().is_some_and(|(best, _)| bound <= *best) {
+            return false;
+        }
+
+        if let Some(key) = self.problem.key(state) {
+            let seen = self.seen.entry(key).or_default();
+            if seen
+                .iter()
+                .any(|other| self.problem.dominates(other, state))
+            {
+                return false;
+            }
+            seen.retain(|other| !self.problem.dominates(state, other));
+            seen.push(state.clone());
+        }
+
+        if let Some(score) = self.problem.score(state) {
+            if self.best.as_ref().is_none_or(|(best, _)| score > *best) {
+                self.best = Some((score, state.clone()));
+            }
+        }
+
+        self.explored += 1;
+        true
+    }
+
+    /// Explores everything that can be reached from `starts`, depth first. Every once in a while,
+    /// `sync` is called to learn about better solutions that were found elsewhere.
+    fn run<F>(&mut self, starts: Vec<B::State>, sync: F)
+    where
+        F: Fn() -> Best<B::State, B::Score>,
+    {
+        let mut stack = vec![];
+        self.push_all(&mut stack, starts);
+        let mut popped = 0;
+        while let Some((bound, state)) = stack.pop() {
+            popped += 1;
+            if popped % SYNC_INTERVAL == 0 {
+                let other = sync();
+                if !self.is_better(other.as_ref()) && other.is_some() {
+                    self.best = other;
+                }
+            }
+
+            if self.visit(bound, &state) {
+                let children = self.problem.children(&state);
+                self.push_all(&mut stack, children);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Split a list of numbers into two groups, so that the product of their sums is maximal.
+    struct Partition(Vec<u64>);
+
+    #[derive(Debug, Clone, Eq, PartialEq)]
+    struct Split {
+        next: usize,
+        left: u64,
+        right: u64,
+    }
+
+    impl BranchAndBound for Partition {
+        type State = Split;
+        type Score = u64;
+        type Key = (usize, u64);
+
+        fn children(&self, state: &Split) -> Vec<Split> {
+            match self.0.get(state.next) {
+                Some(&n) => vec![
+                    Split {
+                        next: state.next + 1,
+                        left: state.left + n,
+                        right: state.right,
+                    },
+                    Split {
+                        next: state.next + 1,
+                        left: state.left,
+                        right: state.right + n,
+                    },
+                ],
+                None => vec![],
+            }
+        }
+
+        fn upper_bound(&self, state: &Split) -> u64 {
+            let total = state.left + state.right + self.0[state.next..].iter().sum::<u64>();
+            (total / 2) * (total - total / 2)
+        }
+
+        fn score(&self, state: &Split) -> Option<u64> {
+            if state.next == self.0.len() {
+                Some(state.left * state.right)
+            } else {
+                None
+            }
+        }
+
+        fn key(&self, state: &Split) -> Option<(usize, u64)> {
+            // the order of the groups does not matter
+            Some((state.next, state.left.min(state.right)))
+        }
+    }
+
+    fn start() -> Split {
+        Split {
+            next: 0,
+            left: 0,
+            right: 0,
+        }
+    }
+
+    #[test]
+    fn test_maximise() {
+        let problem = Partition(vec![3, 1, 4, 1, 5, 9, 2, 6]);
+        let solution = problem.maximise([start()]).unwrap();
+
+        // 31 = 15 + 16
+        assert_eq!(solution.score, 240);
+        assert_eq!(solution.state.left + solution.state.right, 31);
+        assert!(solution.explored > 0);
+    }
+
+    #[test]
+    fn test_maximise_without_solution() {
+        struct Nothing;
+
+        impl BranchAndBound for Nothing {
+            type State = u8;
+            type Score = u8;
+            type Key = ();
+
+            fn children(&self, &state: &u8) -> Vec<u8> {
+                if state < 5 {
+                    vec![state + 1]
+                } else {
+                    vec![]
+                }
+            }
+
+            fn upper_bound(&self, _: &u8) -> u8 {
+                u8::MAX
+            }
+
+            fn score(&self, _: &u8) -> Option<u8> {
+                None
+            }
+        }
+
+        assert_eq!(Nothing.maximise([0]), None);
+        assert_eq!(Nothing.maximise_parallel([0]), None);
+    }
+
+    #[test]
+    fn test_dominance_pruning() {
+        /// Walk along a line of cells with rewards, where you can either take one or two steps at
+        /// a time. Collect as much as possible, ending at the last cell.
+        struct Line(Vec<u32>);
+
+        impl BranchAndBound for Line {
+            type State = (usize, u32);
+            type Score = u32;
+            type Key = usize;
+
+            fn children(&self, &(position, collected): &(usize, u32)) -> Vec<(usize, u32)> {
+                (position + 1..=position + 2)
+                    .filter(|&next| next < self.0.len())
+                    .map(|next| (next, collected + self.0[next]))
+                    .collect()
+            }
+
+            fn upper_bound(&self, &(position, collected): &(usize, u32)) -> u32 {
+                collected + self.0[position + 1..].iter().sum::<u32>()
+            }
+
+            fn score(&self, &(position, collected): &(usize, u32)) -> Option<u32> {
+                (position == self.0.len() - 1).then_some(collected)
+            }
+
+            fn key(&self, &(position, _): &(usize, u32)) -> Option<usize> {
+                Some(position)
+            }
+
+            fn dominates(&self, a: &(usize, u32), b: &(usize, u32)) -> bool {
+                a.1 >= b.1
+            }
+        }
+
+        let line = Line((0..40).map(|i| (i * 7) % 5).collect());
+        let solution = line.maximise([(0, 0)]).unwrap();
+        let expected: u32 = line.0.iter().sum();
+
+        // every cell with a reward can be visited, since all zeros are isolated
+        assert_eq!(solution.score, expected);
+
+        // without dominance pruning, the number of paths would be exponential
+        assert!(solution.explored < 200);
+    }
+
+    #[test]
+    fn test_maximise_parallel() {
+        let problem = Partition((1..=16).collect());
+        let solution = problem.maximise_parallel([start()]).unwrap();
+
+        // 136 = 68 + 68
+        assert_eq!(solution.score, 68 * 68);
+        assert_eq!(solution.state.left, 68);
+    }
+}
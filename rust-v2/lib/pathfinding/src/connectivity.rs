@@ -0,0 +1,454 @@
+//! Algorithms about how the nodes in a [`Graph`] are connected: union-find, connected
+//! components, minimum cuts and maximum flows.
+//!
+//! The functions in this module that treat a graph as undirected (such as
+//! [`connected_components`] and [`stoer_wagner`]) expect every edge to be present in both
+//! directions, which is what [`Graph::add_undirected_edge`] does.
+//!
+//! # Usage
+//!
+//! ```
+//! use rdcl_aoc_pathfinding::connectivity::{connected_components, stoer_wagner};
+//! use rdcl_aoc_pathfinding::graph::Graph;
+//!
+//! // two triangles, connected by a single wire
+//! let mut graph: Graph<&str, u32> = Graph::new();
+//! for (a, b) in [("a", "b"), ("b", "c"), ("c", "a"), ("x", "y"), ("y", "z"), ("z", "x")] {
+//!     graph.add_undirected_edge(a, b, 1);
+//! }
+//! assert_eq!(connected_components(&graph).len(), 2);
+//!
+//! graph.add_undirected_edge("c", "x", 1);
+//! assert_eq!(connected_components(&graph).len(), 1);
+//!
+//! let cut = stoer_wagner(&graph).unwrap();
+//! assert_eq!(cut.weight, 1);
+//! assert_eq!(cut.partition.len(), 3);
+//! ```
+
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+use std::ops::Sub;
+
+use crate::cost::Cost;
+use crate::graph::Graph;
+
+/// A disjoint-set forest, which keeps track of a partitioning of the elements `0..len` into sets.
+/// Uses path compression and union by size, so all operations run in (amortized) nearly constant
+/// time.
+#[derive(Debug, Clone)]
+pub struct UnionFind {
+    parents: Vec<usize>,
+    sizes: Vec<usize>,
+    sets: usize,
+}
+
+impl UnionFind {
+    /// Creates a new union-find, in which every element is in a set of its own.
+    pub fn new(len: usize) -> UnionFind {
+        UnionFind {
+            parents: (0..len).collect(),
+            sizes: vec![1; len],
+            sets: len,
+        }
+    }
+
+    /// Finds the representative of the set that contains `element`.
+    pub fn find(&mut self, element: usize) -> usize {
+        let mut root = element;
+        while self.parents[root] != root {
+            root = self.parents[root];
+        }
+
+        let mut current = element;
+        while self.parents[current] != root {
+            let next = self.parents[current];
+            self.parents[current] = root;
+            current = next;
+        }
+
+        root
+    }
+
+    /// Merges the sets that contain `a` and `b`. Returns `false` if they were already in the same
+    /// set.
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let a = self.find(a);
+        let b = self.find(b);
+        if a == b {
+            return false;
+        }
+
+        let (large, small) = if self.sizes[a] < self.sizes[b] {
+            (b, a)
+        } else {
+            (a, b)
+        };
+        self.parents[small] = large;
+        self.sizes[large] += self.sizes[small];
+        self.sets -= 1;
+        true
+    }
+
+    /// Returns `true` if `a` and `b` are in the same set.
+    pub fn same_set(&mut self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+
+    /// The size of the set that contains `element`.
+    pub fn set_size(&mut self, element: usize) -> usize {
+        let root = self.find(element);
+        self.sizes[root]
+    }
+
+    /// The number of disjoint sets.
+    pub fn sets(&self) -> usize {
+        self.sets
+    }
+
+    /// The number of elements.
+    pub fn len(&self) -> usize {
+        self.parents.len()
+    }
+
+    /// Returns `true` if there are no elements.
+    pub fn is_empty(&self) -> bool {
+        self.parents.is_empty()
+    }
+
+    /// All sets, ordered by their smallest element. The elements within a set are sorted.
+    pub fn into_sets(mut self) -> Vec<Vec<usize>> {
+        let mut sets: Vec<Vec<usize>> = vec![];
+        let mut index_of_root = HashMap::new();
+        for element in 0..self.len() {
+            let root = self.find(element);
+            let index = *index_of_root.entry(root).or_insert_with(|| {
+                sets.push(vec![]);
+                sets.len() - 1
+            });
+            sets[index].push(element);
+        }
+        sets
+    }
+}
+
+/// A cut that splits a graph in two.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Cut<C> {
+    /// The total weight of the edges that cross the cut.
+    pub weight: C,
+    /// The indices of the nodes on one side of the cut, in ascending order. All other nodes are
+    /// on the other side.
+    pub partition: Vec<usize>,
+}
+
+/// Finds the connected components of an undirected graph. Every component is a list of node
+/// indices in ascending order, and the components are ordered by their smallest node.
+pub fn connected_components<N, C>(graph: &Graph<N, C>) -> Vec<Vec<usize>>
+where
+    N: Hash + Eq + Clone,
+    C: Cost,
+{
+    let mut union_find = UnionFind::new(graph.len());
+    for node in 0..graph.len() {
+        for &(next, _) in graph.edges(node) {
+            union_find.union(node, next);
+        }
+    }
+    union_find.into_sets()
+}
+
+/// Uses the [Stoer–Wagner algorithm](https://en.wikipedia.org/wiki/Stoer%E2%80%93Wagner_algorithm)
+/// to find a global minimum cut of an undirected graph, i.e. a way to split the graph in two so
+/// that the total weight of the edges between the two halves is minimal. Parallel edges are
+/// combined by adding their weights. Returns `None` if the graph has fewer than two nodes.
+///
+/// # Panics
+///
+/// Panics if the sum of the weights of the edges overflows.
+pub fn stoer_wagner<N, C>(graph: &Graph<N, C>) -> Option<Cut<C>>
+where
+    N: Hash + Eq + Clone,
+    C: Cost,
+{
+    if graph.len() < 2 {
+        return None;
+    }
+
+    let add = |a: C, b: C| a.checked_add(b).expect("the weight of the cut overflows");
+
+    // the weighted adjacency of the contracted graph, and which original nodes every contracted
+    // node consists of
+    let mut adjacency: Vec<HashMap<usize, C>> = vec![HashMap::new(); graph.len()];
+    for (node, weights) in adjacency.iter_mut().enumerate() {
+        for &(next, weight) in graph.edges(node) {
+            if next != node {
+                let total = weights.entry(next).or_insert(C::ZERO);
+                *total = add(*total, weight);
+            }
+        }
+    }
+    let mut members: Vec<Vec<usize>> = (0..graph.len()).map(|node| vec![node]).collect();
+    let mut active: Vec<usize> = (0..graph.len()).collect();
+    let mut best: Option<Cut<C>> = None;
+
+    while active.len() > 1 {
+        // Minimum cut phase: repeatedly add the node that is most tightly connected to the nodes
+        // that were added so far. The last node that is added, separated from all others, is a
+        // minimum cut between the last two nodes.
+        let mut added = vec![false; graph.len()];
+        let mut connectivity: HashMap<usize, C> = HashMap::new();
+        let mut queue: BinaryHeap<(C, usize)> =
+            active.iter().map(|&node| (C::ZERO, node)).collect();
+        let mut order = Vec::with_capacity(active.len());
+
+        while let Some((weight, node)) = queue.pop() {
+            if added[node] || connectivity.get(&node).is_some_and(|&w| w != weight) {
+                continue;
+            }
+            added[node] = true;
+            order.push((node, weight));
+
+            for (&next, &edge) in &adjacency[node] {
+                if !added[next] {
+                    let total = connectivity.entry(next).or_insert(C::ZERO);
+                    *total = add(*total, edge);
+                    queue.push((*total, next));
+                }
+            }
+        }
+
+        let (last, weight) = order[order.len() - 1];
+        let (previous, _) = order[order.len() - 2];
+        if best.as_ref().map(|cut| weight < cut.weight).unwrap_or(true) {
+            let mut partition = members[last].clone();
+            partition.sort_unstable();
+            best = Some(Cut { weight, partition });
+        }
+
+        // merge the last node into the one before it
+        let merged = std::mem::take(&mut adjacency[last]);
+        for (next, edge) in merged {
+            adjacency[next].remove(&last);
+            if next != previous {
+                let total = adjacency[previous].entry(next).or_insert(C::ZERO);
+                *total = add(*total, edge);
+                let total = adjacency[next].entry(previous).or_insert(C::ZERO);
+                *total = add(*total, edge);
+            }
+        }
+        let moved = std::mem::take(&mut members[last]);
+        members[previous].extend(moved);
+        active.retain(|&node| node != last);
+    }
+
+    best
+}
+
+/// Uses the [Edmonds–Karp algorithm](https://en.wikipedia.org/wiki/Edmonds%E2%80%93Karp_algorithm)
+/// to find a maximum flow from `source` to `sink`, where the cost of every edge is its capacity.
+/// Capacities must not be negative.
+///
+/// The value of a maximum flow equals the weight of a minimum cut between `source` and `sink`, so
+/// the result is returned as a [`Cut`] in which the partition contains the nodes on the side of
+/// `source`.
+///
+/// # Panics
+///
+/// Panics if `source` and `sink` are the same node, or if the total capacity overflows.
+pub fn edmonds_karp<N, C>(graph: &Graph<N, C>, source: usize, sink: usize) -> Cut<C>
+where
+    N: Hash + Eq + Clone,
+    C: Cost + Sub<Output = C>,
+{
+    assert_ne!(source, sink, "the source and the sink must be different");
+
+    let add = |a: C, b: C| a.checked_add(b).expect("the capacity overflows");
+
+    let mut residual: Vec<HashMap<usize, C>> = vec![HashMap::new(); graph.len()];
+    for node in 0..graph.len() {
+        for &(next, capacity) in graph.edges(node) {
+            let total = residual[node].entry(next).or_insert(C::ZERO);
+            *total = add(*total, capacity);
+            residual[next].entry(node).or_insert(C::ZERO);
+        }
+    }
+
+    let mut flow = C::ZERO;
+    loop {
+        // find the shortest augmenting path
+        let mut predecessors: Vec<Option<usize>> = vec![None; graph.len()];
+        predecessors[source] = Some(source);
+        let mut queue = VecDeque::from([source]);
+        while let Some(node) = queue.pop_front() {
+            if node == sink {
+                break;
+            }
+            for (&next, &capacity) in &residual[node] {
+                if predecessors[next].is_none() && capacity > C::ZERO {
+                    predecessors[next] = Some(node);
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        if predecessors[sink].is_none() {
+            let partition = (0..graph.len())
+                .filter(|&node| predecessors[node].is_some())
+                .collect();
+            return Cut {
+                weight: flow,
+                partition,
+            };
+        }
+
+        let mut bottleneck = None;
+        let mut node = sink;
+        while node != source {
+            let previous = predecessors[node].unwrap();
+            let capacity = residual[previous][&node];
+            bottleneck = Some(bottleneck.map_or(capacity, |b: C| b.min(capacity)));
+            node = previous;
+        }
+        let bottleneck = bottleneck.unwrap();
+
+        let mut node = sink;
+        while node != source {
+            let previous = predecessors[node].unwrap();
+            let forward = residual[previous].get_mut(&node).unwrap();
+            *forward = *forward - bottleneck;
+            let backward = residual[node].get_mut(&previous).unwrap();
+            *backward = add(*backward, bottleneck);
+            node = previous;
+        }
+        flow = add(flow, bottleneck);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn graph(edges: &[(u32, u32, u32)]) -> Graph<u32, u32> {
+        let mut graph = Graph::new();
+        for &(a, b, weight) in edges {
+            graph.add_undirected_edge(a, b, weight);
+        }
+        graph
+    }
+
+    fn labels(graph: &Graph<u32, u32>, indices: &[usize]) -> Vec<u32> {
+        let mut labels: Vec<u32> = indices.iter().map(|&i| *graph.node(i)).collect();
+        labels.sort_unstable();
+        labels
+    }
+
+    #[test]
+    fn test_union_find() {
+        let mut union_find = UnionFind::new(6);
+        assert_eq!(union_find.sets(), 6);
+
+        assert!(union_find.union(0, 1));
+        assert!(union_find.union(2, 3));
+        assert!(union_find.union(1, 3));
+        assert!(!union_find.union(0, 2));
+
+        assert!(union_find.same_set(0, 3));
+        assert!(!union_find.same_set(0, 4));
+        assert_eq!(union_find.set_size(2), 4);
+        assert_eq!(union_find.sets(), 3);
+        assert_eq!(
+            union_find.into_sets(),
+            vec![vec![0, 1, 2, 3], vec![4], vec![5]]
+        );
+    }
+
+    #[test]
+    fn test_connected_components() {
+        let mut graph = graph(&[(1, 2, 1), (3, 4, 1), (2, 5, 1)]);
+        graph.add_node(6);
+
+        let components = connected_components(&graph);
+        assert_eq!(components.len(), 3);
+        assert_eq!(labels(&graph, &components[0]), vec![1, 2, 5]);
+        assert_eq!(labels(&graph, &components[1]), vec![3, 4]);
+        assert_eq!(labels(&graph, &components[2]), vec![6]);
+    }
+
+    #[test]
+    fn test_stoer_wagner() {
+        // the example from the original paper, with a minimum cut of weight 4
+        let graph = graph(&[
+            (1, 2, 2),
+            (1, 5, 3),
+            (2, 3, 3),
+            (2, 5, 2),
+            (2, 6, 2),
+            (3, 4, 4),
+            (3, 7, 2),
+            (4, 7, 2),
+            (4, 8, 2),
+            (5, 6, 3),
+            (6, 7, 1),
+            (7, 8, 3),
+        ]);
+
+        let cut = stoer_wagner(&graph).unwrap();
+        assert_eq!(cut.weight, 4);
+        let side = labels(&graph, &cut.partition);
+        assert!(side == vec![3, 4, 7, 8] || side == vec![1, 2, 5, 6]);
+    }
+
+    #[test]
+    fn test_stoer_wagner_disconnected() {
+        let mut graph = graph(&[(1, 2, 5)]);
+        graph.add_node(3);
+
+        let cut = stoer_wagner(&graph).unwrap();
+        assert_eq!(cut.weight, 0);
+    }
+
+    #[test]
+    fn test_stoer_wagner_too_small() {
+        let mut graph: Graph<u32, u32> = Graph::new();
+        assert_eq!(stoer_wagner(&graph), None);
+        graph.add_node(1);
+        assert_eq!(stoer_wagner(&graph), None);
+    }
+
+    #[test]
+    fn test_edmonds_karp() {
+        let mut graph: Graph<char, u32> = Graph::new();
+        for (a, b, capacity) in [
+            ('s', 'a', 10),
+            ('s', 'c', 10),
+            ('a', 'b', 4),
+            ('a', 'c', 2),
+            ('a', 'd', 8),
+            ('c', 'd', 9),
+            ('d', 'b', 6),
+            ('b', 't', 10),
+            ('d', 't', 10),
+        ] {
+            graph.add_edge(a, b, capacity);
+        }
+        let source = graph.index_of(&'s').unwrap();
+        let sink = graph.index_of(&'t').unwrap();
+
+        let cut = edmonds_karp(&graph, source, sink);
+        assert_eq!(cut.weight, 19);
+        assert!(cut.partition.contains(&source));
+        assert!(!cut.partition.contains(&sink));
+    }
+
+    #[test]
+    fn test_edmonds_karp_undirected() {
+        let graph = graph(&[(1, 2, 3), (2, 3, 1), (1, 3, 1), (3, 4, 5)]);
+        let source = graph.index_of(&1).unwrap();
+        let sink = graph.index_of(&4).unwrap();
+
+        let cut = edmonds_karp(&graph, source, sink);
+        assert_eq!(cut.weight, 2);
+        assert_eq!(labels(&graph, &cut.partition), vec![1, 2]);
+    }
+}
@@ -0,0 +1,76 @@
+//! The costs (or distances) that are used by the searches in this crate.
+
+use std::fmt::Debug;
+
+/// A cost that can be accumulated along a path. Costs must be totally ordered, and must never be
+/// negative: the searches panic if they come across an edge with a negative cost. Adding costs
+/// must be overflow-safe: an addition that overflows results in `None`, in which case the search
+/// ignores the path. This never affects the result, since if a path fits in the cost type at all,
+/// the shortest one does.
+///
+/// This trait is implemented for all primitive integer types. Other types (e.g. exact rational
+/// numbers) can be used by implementing this trait for them.
+pub trait Cost: Copy + Ord + Debug {
+    /// The cost of an empty path.
+    const ZERO: Self;
+
+    /// Adds two costs, or returns `None` if the result cannot be represented.
+    fn checked_add(self, other: Self) -> Option<Self>;
+}
+
+macro_rules! impl_cost {
+    ($($type:ty),+) => {
+        $(
+            impl Cost for $type {
+                const ZERO: Self = 0;
+
+                #[inline]
+                fn checked_add(self, other: Self) -> Option<Self> {
+                    <$type>::checked_add(self, other)
+                }
+            }
+        )+
+    };
+}
+
+impl_cost!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+/// Extends a path with an edge, or returns `None` if the cost of the extended path cannot be
+/// represented.
+///
+/// # Panics
+///
+/// Panics if the cost of the edge is negative.
+pub(crate) fn extend<C: Cost>(path: C, edge: C) -> Option<C> {
+    assert!(
+        edge >= C::ZERO,
+        "Found an edge with a negative cost: {:?}",
+        edge
+    );
+    path.checked_add(edge)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_checked_add() {
+        assert_eq!(Cost::checked_add(3u64, 4), Some(7));
+        assert_eq!(Cost::checked_add(3i32, 4), Some(7));
+        assert_eq!(Cost::checked_add(u8::MAX, 1), None);
+        assert_eq!(<u32 as Cost>::ZERO, 0);
+    }
+
+    #[test]
+    fn test_extend() {
+        assert_eq!(extend(3u8, 4), Some(7));
+        assert_eq!(extend(u8::MAX, 1), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Found an edge with a negative cost: -1")]
+    fn test_extend_negative() {
+        extend(3i32, -1);
+    }
+}
@@ -0,0 +1,347 @@
+//! Distance metrics and neighbour generators for points in an N-dimensional grid, where points are
+//! represented as arrays of coordinates.
+//!
+//! Every metric comes with a matching neighbour generator: the neighbours of a point are exactly
+//! the points at distance 1. This makes the metrics admissible heuristics for [`AStar`] when the
+//! neighbours are generated with the matching generator and every step costs 1.
+//!
+//! Neighbours that cannot be represented (e.g. a negative coordinate for an unsigned type) are
+//! skipped. The metrics panic if the distance cannot be represented in the coordinate type (e.g.
+//! the distance between `i8::MIN` and `i8::MAX`). Use a wider type if this may happen.
+//!
+//! # Usage
+//!
+//! ```
+//! use rdcl_aoc_pathfinding::distance::{manhattan, orthogonal_neighbours};
+//! use rdcl_aoc_pathfinding::AStar;
+//!
+//! struct Open;
+//!
+//! impl AStar<u32> for Open {
+//!     type Point = [u32; 3];
+//!     type EndPoint = [u32; 3];
+//!
+//!     fn distance_score(&self, a: &[u32; 3], b: &[u32; 3]) -> u32 {
+//!         manhattan(*a, *b)
+//!     }
+//!
+//!     fn get_neighbours(&self, point: &[u32; 3]) -> Vec<(u32, [u32; 3])> {
+//!         orthogonal_neighbours(*point).into_iter().map(|p| (1, p)).collect()
+//!     }
+//! }
+//!
+//! let path = Open.find_path(&[0, 0, 0], &[1, 2, 3]).unwrap();
+//! assert_eq!(path.cost, 6);
+//! ```
+//!
+//! [`AStar`]: crate::AStar
+
+use std::fmt::Debug;
+
+/// A coordinate of a point in a grid. This trait is implemented for all primitive integer types.
+pub trait Coordinate: Copy + Ord + Debug {
+    /// The value zero.
+    const ZERO: Self;
+    /// The value one.
+    const ONE: Self;
+
+    /// Adds two coordinates, or returns `None` if the result cannot be represented.
+    fn checked_add(self, other: Self) -> Option<Self>;
+
+    /// Subtracts two coordinates, or returns `None` if the result cannot be represented.
+    fn checked_sub(self, other: Self) -> Option<Self>;
+
+    /// Multiplies two coordinates, or returns `None` if the result cannot be represented.
+    fn checked_mul(self, other: Self) -> Option<Self>;
+}
+
+macro_rules! impl_coordinate {
+    ($($type:ty),+) => {
+        $(
+            impl Coordinate for $type {
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+
+                #[inline]
+                fn checked_add(self, other: Self) -> Option<Self> {
+                    <$type>::checked_add(self, other)
+                }
+
+                #[inline]
+                fn checked_sub(self, other: Self) -> Option<Self> {
+                    <$type>::checked_sub(self, other)
+                }
+
+                #[inline]
+                fn checked_mul(self, other: Self) -> Option<Self> {
+                    <$type>::checked_mul(self, other)
+                }
+            }
+        )+
+    };
+}
+
+impl_coordinate!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+/// Unwraps the result of a checked operation on coordinates.
+fn fits<T: Coordinate>(value: Option<T>) -> T {
+    value.expect("The distance does not fit in the coordinate type")
+}
+
+/// The absolute difference between two coordinates.
+fn diff<T: Coordinate>(a: T, b: T) -> T {
+    if a < b {
+        fits(b.checked_sub(a))
+    } else {
+        fits(a.checked_sub(b))
+    }
+}
+
+/// The [Manhattan distance](https://en.wikipedia.org/wiki/Taxicab_geometry) between two points,
+/// i.e. the sum of the absolute differences of their coordinates.
+///
+/// # Panics
+///
+/// Panics if the distance cannot be represented in the coordinate type.
+pub fn manhattan<T: Coordinate, const N: usize>(a: [T; N], b: [T; N]) -> T {
+    a.into_iter().zip(b).fold(T::ZERO, |distance, (a, b)| {
+        fits(distance.checked_add(diff(a, b)))
+    })
+}
+
+/// The [Chebyshev distance](https://en.wikipedia.org/wiki/Chebyshev_distance) between two points,
+/// i.e. the largest absolute difference of their coordinates.
+///
+/// # Panics
+///
+/// Panics if the distance cannot be represented in the coordinate type.
+pub fn chebyshev<T: Coordinate, const N: usize>(a: [T; N], b: [T; N]) -> T {
+    a.into_iter()
+        .zip(b)
+        .fold(T::ZERO, |distance, (a, b)| distance.max(diff(a, b)))
+}
+
+/// The square of the Euclidean distance between two points. Unlike the Euclidean distance itself,
+/// this is always an integer, and it preserves the ordering of distances.
+///
+/// Note that this is not an admissible heuristic for [`AStar`](crate::AStar), since it can
+/// overestimate the number of steps.
+///
+/// # Panics
+///
+/// Panics if the distance cannot be represented in the coordinate type.
+pub fn euclidean_squared<T: Coordinate, const N: usize>(a: [T; N], b: [T; N]) -> T {
+    a.into_iter().zip(b).fold(T::ZERO, |distance, (a, b)| {
+        let d = diff(a, b);
+        fits(distance.checked_add(fits(d.checked_mul(d))))
+    })
+}
+
+/// The distance between two hexagons in
+/// [cube coordinates](https://www.redblobgames.com/grids/hexagons/#coordinates-cube) `[q, r, s]`,
+/// where `q + r + s == 0`.
+///
+/// # Panics
+///
+/// Panics if the distance cannot be represented in the coordinate type.
+pub fn hex_cube_distance<T: Coordinate>(a: [T; 3], b: [T; 3]) -> T {
+    chebyshev(a, b)
+}
+
+/// The distance between two hexagons in
+/// [axial coordinates](https://www.redblobgames.com/grids/hexagons/#coordinates-axial) `[q, r]`.
+///
+/// # Panics
+///
+/// Panics if the distance cannot be represented in the coordinate type.
+pub fn hex_axial_distance<T: Coordinate>([qa, ra]: [T; 2], [qb, rb]: [T; 2]) -> T {
+    // the third cube coordinate is s = -q - r, so |s_a - s_b| = |(q_a - q_b) + (r_a - r_b)|,
+    // which is computed without intermediate values that might not fit in the coordinate type
+    let dq = diff(qa, qb);
+    let dr = diff(ra, rb);
+    let ds = if (qa < qb) == (ra < rb) {
+        fits(dq.checked_add(dr))
+    } else {
+        diff(dq, dr)
+    };
+    dq.max(dr).max(ds)
+}
+
+/// The points that are at a [`manhattan`] distance of 1 from `point`, i.e. the points that differ
+/// by 1 in exactly one coordinate.
+pub fn orthogonal_neighbours<T: Coordinate, const N: usize>(point: [T; N]) -> Vec<[T; N]> {
+    let mut neighbours = Vec::with_capacity(2 * N);
+    for axis in 0..N {
+        if let Some(c) = point[axis].checked_sub(T::ONE) {
+            let mut neighbour = point;
+            neighbour[axis] = c;
+            neighbours.push(neighbour);
+        }
+        if let Some(c) = point[axis].checked_add(T::ONE) {
+            let mut neighbour = point;
+            neighbour[axis] = c;
+            neighbours.push(neighbour);
+        }
+    }
+    neighbours
+}
+
+/// The points that are at a [`chebyshev`] distance of 1 from `point`, i.e. all points that differ
+/// by at most 1 in every coordinate, including the diagonals. There are `3^N - 1` of them.
+pub fn all_neighbours<T: Coordinate, const N: usize>(point: [T; N]) -> Vec<[T; N]> {
+    let mut neighbours = vec![point];
+    for axis in 0..N {
+        let mut next = Vec::with_capacity(neighbours.len() * 3);
+        for p in neighbours {
+            if let Some(c) = p[axis].checked_sub(T::ONE) {
+                let mut neighbour = p;
+                neighbour[axis] = c;
+                next.push(neighbour);
+            }
+            next.push(p);
+            if let Some(c) = p[axis].checked_add(T::ONE) {
+                let mut neighbour = p;
+                neighbour[axis] = c;
+                next.push(neighbour);
+            }
+        }
+        neighbours = next;
+    }
+    neighbours.retain(|&p| p != point);
+    neighbours
+}
+
+/// The six neighbours of a hexagon in cube coordinates (see [`hex_cube_distance`]).
+pub fn hex_cube_neighbours<T: Coordinate>(point: [T; 3]) -> Vec<[T; 3]> {
+    // every neighbour adds 1 to one coordinate and subtracts 1 from another
+    let mut neighbours = Vec::with_capacity(6);
+    for plus in 0..3 {
+        for minus in 0..3 {
+            if plus != minus {
+                let mut neighbour = point;
+                let (Some(p), Some(m)) = (
+                    point[plus].checked_add(T::ONE),
+                    point[minus].checked_sub(T::ONE),
+                ) else {
+                    continue;
+                };
+                neighbour[plus] = p;
+                neighbour[minus] = m;
+                neighbours.push(neighbour);
+            }
+        }
+    }
+    neighbours
+}
+
+/// The six neighbours of a hexagon in axial coordinates (see [`hex_axial_distance`]).
+pub fn hex_axial_neighbours<T: Coordinate>([q, r]: [T; 2]) -> Vec<[T; 2]> {
+    let one = T::ONE;
+    [
+        (q.checked_add(one), Some(r)),
+        (q.checked_sub(one), Some(r)),
+        (Some(q), r.checked_add(one)),
+        (Some(q), r.checked_sub(one)),
+        (q.checked_add(one), r.checked_sub(one)),
+        (q.checked_sub(one), r.checked_add(one)),
+    ]
+    .into_iter()
+    .filter_map(|neighbour| match neighbour {
+        (Some(q), Some(r)) => Some([q, r]),
+        _ => None,
+    })
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_manhattan() {
+        assert_eq!(manhattan([0, 0], [3, -4]), 7);
+        assert_eq!(manhattan([1u8, 5, 2], [4, 1, 2]), 7);
+        assert_eq!(manhattan([1, 2, 3, 4], [4, 3, 2, 1]), 8);
+        assert_eq!(manhattan::<i32, 0>([], []), 0);
+    }
+
+    #[test]
+    fn test_chebyshev() {
+        assert_eq!(chebyshev([0, 0], [3, -4]), 4);
+        assert_eq!(chebyshev([1u8, 5, 2], [4, 1, 2]), 4);
+    }
+
+    #[test]
+    fn test_euclidean_squared() {
+        assert_eq!(euclidean_squared([0, 0], [3, -4]), 25);
+        assert_eq!(euclidean_squared([1u8, 5, 2], [4, 1, 2]), 25);
+        assert_eq!(euclidean_squared([0u8], [15]), 225);
+    }
+
+    #[test]
+    #[should_panic(expected = "The distance does not fit in the coordinate type")]
+    fn test_euclidean_squared_overflow() {
+        euclidean_squared([0u8], [16]);
+    }
+
+    #[test]
+    #[should_panic(expected = "The distance does not fit in the coordinate type")]
+    fn test_manhattan_overflow() {
+        manhattan([i8::MIN], [i8::MAX]);
+    }
+
+    #[test]
+    fn test_hex_distance() {
+        assert_eq!(hex_cube_distance([0, 0, 0], [3, -1, -2]), 3);
+        assert_eq!(hex_axial_distance([0, 0], [3, -1]), 3);
+        assert_eq!(hex_axial_distance([0, 0], [2, 2]), 4);
+        assert_eq!(hex_axial_distance([-1, 3], [1, 1]), 2);
+        assert_eq!(hex_axial_distance([200u8, 100], [200, 100]), 0);
+        assert_eq!(hex_axial_distance([200u8, 100], [100, 200]), 100);
+        assert_eq!(hex_axial_distance([100u8, 100], [200, 200]), 200);
+
+        for (a, b) in [([-3, 5], [2, -1]), ([4, 4], [-4, 7]), ([0, -2], [-5, 0])] {
+            let cube = |[q, r]: [i32; 2]| [q, r, -q - r];
+            assert_eq!(
+                hex_axial_distance(a, b),
+                hex_cube_distance(cube(a), cube(b))
+            );
+        }
+    }
+
+    #[test]
+    fn test_orthogonal_neighbours() {
+        let mut neighbours = orthogonal_neighbours([0, 0]);
+        neighbours.sort_unstable();
+        assert_eq!(neighbours, vec![[-1, 0], [0, -1], [0, 1], [1, 0]]);
+
+        let mut neighbours = orthogonal_neighbours([0u32, 5]);
+        neighbours.sort_unstable();
+        assert_eq!(neighbours, vec![[0, 4], [0, 6], [1, 5]]);
+    }
+
+    #[test]
+    fn test_all_neighbours() {
+        let neighbours = all_neighbours([1, 1, 1]);
+        assert_eq!(neighbours.len(), 26);
+        assert!(neighbours.iter().all(|&p| chebyshev(p, [1, 1, 1]) == 1));
+
+        assert_eq!(all_neighbours([0u8, 0]).len(), 3);
+    }
+
+    #[test]
+    fn test_hex_neighbours() {
+        let neighbours = hex_cube_neighbours([0, 0, 0]);
+        assert_eq!(neighbours.len(), 6);
+        for [q, r, s] in neighbours {
+            assert_eq!(q + r + s, 0);
+            assert_eq!(hex_cube_distance([q, r, s], [0, 0, 0]), 1);
+        }
+
+        let neighbours = hex_axial_neighbours([2, -1]);
+        assert_eq!(neighbours.len(), 6);
+        for neighbour in neighbours {
+            assert_eq!(hex_axial_distance(neighbour, [2, -1]), 1);
+        }
+    }
+}
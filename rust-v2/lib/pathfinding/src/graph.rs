@@ -0,0 +1,557 @@
+//! Explicit, weighted graphs, and searches that need to look at the graph as a whole (such as
+//! finding the longest simple path).
+//!
+//! Puzzles often describe a maze in which most points are part of long corridors, and only a few
+//! points are junctions where a choice has to be made. [`compress_corridors`] turns such a maze
+//! into a [`Graph`] that only contains the junctions, connected by edges whose cost is the length
+//! of the corridor between them. Searches that are too expensive on the full maze (such as
+//! [`Graph::longest_path`]) are usually feasible on the compressed graph.
+//!
+//! Nodes in a graph are identified by their index, which is assigned in the order in which the
+//! nodes were added. Use [`Graph::index_of`] and [`Graph::node`] to convert between nodes and
+//! indices.
+//!
+//! # Usage
+//!
+//! ```
+//! use rdcl_aoc_pathfinding::graph::compress_corridors;
+//!
+//! // A maze with two routes from S to E:
+//! //
+//! //   S . . .
+//! //   .     .
+//! //   . . . . . E
+//! let open = [
+//!     (0, 0), (0, 1), (0, 2), (0, 3),
+//!     (1, 0), (1, 3),
+//!     (2, 0), (2, 1), (2, 2), (2, 3), (2, 4), (2, 5),
+//! ];
+//! let neighbours = |&(r, c): &(i32, i32)| {
+//!     [(r - 1, c), (r + 1, c), (r, c - 1), (r, c + 1)]
+//!         .into_iter()
+//!         .filter(|p| open.contains(p))
+//! };
+//!
+//! let graph = compress_corridors((0, 0), [(2, 5)], neighbours);
+//! let start = graph.index_of(&(0, 0)).unwrap();
+//! let end = graph.index_of(&(2, 5)).unwrap();
+//!
+//! // S, E, and the junction at (2, 3)
+//! assert_eq!(graph.len(), 3);
+//! assert_eq!(graph.longest_path(start, end), Some(7));
+//! assert_eq!(graph.all_simple_paths(start, end).len(), 2);
+//! ```
+
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+
+use crate::cost::Cost;
+
+/// The set of nodes that is used by the bitset based searches. This limits these searches to
+/// graphs of at most 128 nodes.
+type NodeSet = u128;
+
+/// The maximum number of nodes that are supported by [`Graph::longest_path`].
+pub const MAX_BITSET_NODES: usize = NodeSet::BITS as usize;
+
+/// A directed graph with weighted edges. Undirected edges are represented by a pair of directed
+/// edges, see [`Graph::add_undirected_edge`].
+#[derive(Debug, Clone)]
+pub struct Graph<N, C = u64> {
+    nodes: Vec<N>,
+    indices: HashMap<N, usize>,
+    edges: Vec<Vec<(usize, C)>>,
+}
+
+/// Tells [`Graph::for_each_simple_path`] whether a path should be extended any further.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Visit {
+    /// Keep extending the path.
+    Continue,
+    /// Do not extend the path any further.
+    Prune,
+}
+
+impl<N, C> Default for Graph<N, C> {
+    fn default() -> Self {
+        Graph {
+            nodes: vec![],
+            indices: HashMap::new(),
+            edges: vec![],
+        }
+    }
+}
+
+impl<N, C> Graph<N, C>
+where
+    N: Hash + Eq + Clone,
+    C: Cost,
+{
+    /// Creates an empty graph.
+    pub fn new() -> Graph<N, C> {
+        Graph::default()
+    }
+
+    /// Adds a node to the graph, and returns its index. If the node already exists, its existing
+    /// index is returned.
+    pub fn add_node(&mut self, node: N) -> usize {
+        if let Some(&index) = self.indices.get(&node) {
+            return index;
+        }
+
+        let index = self.nodes.len();
+        self.indices.insert(node.clone(), index);
+        self.nodes.push(node);
+        self.edges.push(vec![]);
+        index
+    }
+
+    /// Adds a directed edge from `from` to `to`. Nodes that do not exist yet are added.
+    pub fn add_edge(&mut self, from: N, to: N, cost: C) {
+        let from = self.add_node(from);
+        let to = self.add_node(to);
+        self.edges[from].push((to, cost));
+    }
+
+    /// Adds an edge from `a` to `b`, and an edge from `b` to `a`.
+    pub fn add_undirected_edge(&mut self, a: N, b: N, cost: C) {
+        self.add_edge(a.clone(), b.clone(), cost);
+        self.add_edge(b, a, cost);
+    }
+
+    /// The index of `node`, or `None` if the node is not part of the graph.
+    pub fn index_of(&self, node: &N) -> Option<usize> {
+        self.indices.get(node).copied()
+    }
+
+    /// The node with the given index.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no node with this index.
+    pub fn node(&self, index: usize) -> &N {
+        &self.nodes[index]
+    }
+
+    /// All nodes, ordered by their index.
+    pub fn nodes(&self) -> &[N] {
+        &self.nodes
+    }
+
+    /// The outgoing edges of the node with the given index, as `(target, cost)` pairs.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no node with this index.
+    pub fn edges(&self, index: usize) -> &[(usize, C)] {
+        &self.edges[index]
+    }
+
+    /// The number of nodes in the graph.
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Returns `true` if the graph contains no nodes.
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Finds the cost of the most expensive simple path (i.e. a path that visits every node at
+    /// most once) from `start` to `end`. Returns `None` if `end` cannot be reached from `start`.
+    ///
+    /// This problem is NP-hard, so the search is exhaustive. To keep it feasible, the results are
+    /// memoized on the current node together with the set of unvisited nodes that can still be
+    /// reached from it: the best remaining path only depends on those, and different routes to
+    /// the same node often cut off the same part of the graph. Branches from which `end` can no
+    /// longer be reached are pruned immediately.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the graph contains more than [`MAX_BITSET_NODES`] nodes.
+    pub fn longest_path(&self, start: usize, end: usize) -> Option<C> {
+        assert!(
+            self.len() <= MAX_BITSET_NODES,
+            "longest_path supports at most {} nodes, but the graph has {} nodes",
+            MAX_BITSET_NODES,
+            self.len()
+        );
+
+        let mut memo = HashMap::new();
+        self.longest_path_from(start, end, 1 << start, &mut memo)
+    }
+
+    fn longest_path_from(
+        &self,
+        node: usize,
+        end: usize,
+        visited: NodeSet,
+        memo: &mut HashMap<(usize, NodeSet), Option<C>>,
+    ) -> Option<C> {
+        if node == end {
+            return Some(C::ZERO);
+        }
+
+        let reachable = self.reachable_from(node, visited);
+        if reachable & (1 << end) == 0 {
+            return None;
+        }
+
+        if let Some(&longest) = memo.get(&(node, reachable)) {
+            return longest;
+        }
+
+        let mut longest = None;
+        for &(next, cost) in &self.edges[node] {
+            if visited & (1 << next) == 0 {
+                let remaining = self.longest_path_from(next, end, visited | (1 << next), memo);
+                if let Some(total) = remaining.and_then(|remaining| cost.checked_add(remaining)) {
+                    longest = longest.max(Some(total));
+                }
+            }
+        }
+
+        memo.insert((node, reachable), longest);
+        longest
+    }
+
+    /// The set of nodes that can be reached from `node` without passing through `visited`.
+    fn reachable_from(&self, node: usize, visited: NodeSet) -> NodeSet {
+        let mut reachable: NodeSet = 0;
+        let mut stack = vec![node];
+        while let Some(current) = stack.pop() {
+            for &(next, _) in &self.edges[current] {
+                let bit = 1 << next;
+                if (visited | reachable) & bit == 0 {
+                    reachable |= bit;
+                    stack.push(next);
+                }
+            }
+        }
+        reachable
+    }
+
+    /// Calls `visit` for every simple path (i.e. a path that visits every node at most once) that
+    /// starts at `start`, including the path that only consists of `start`. The callback receives
+    /// the indices of the nodes on the path and the total cost of the path, and decides whether the
+    /// path should be extended any further. Paths are visited depth first.
+    ///
+    /// Pruning is what keeps this feasible: the number of simple paths in a graph grows
+    /// exponentially, so the callback should return [`Visit::Prune`] as soon as a path can no
+    /// longer lead to anything useful (e.g. because it exceeds some budget).
+    pub fn for_each_simple_path<F>(&self, start: usize, mut visit: F)
+    where
+        F: FnMut(&[usize], C) -> Visit,
+    {
+        let mut path = vec![start];
+        let mut on_path = vec![false; self.len()];
+        on_path[start] = true;
+        self.simple_paths_from(&mut path, &mut on_path, C::ZERO, &mut visit);
+    }
+
+    fn simple_paths_from<F>(
+        &self,
+        path: &mut Vec<usize>,
+        on_path: &mut [bool],
+        cost: C,
+        visit: &mut F,
+    ) where
+        F: FnMut(&[usize], C) -> Visit,
+    {
+        if visit(path, cost) == Visit::Prune {
+            return;
+        }
+
+        let node = path[path.len() - 1];
+        for &(next, edge_cost) in &self.edges[node] {
+            if on_path[next] {
+                continue;
+            }
+
+            if let Some(cost) = cost.checked_add(edge_cost) {
+                path.push(next);
+                on_path[next] = true;
+                self.simple_paths_from(path, on_path, cost, visit);
+                on_path[next] = false;
+                path.pop();
+            }
+        }
+    }
+
+    /// Returns every simple path from `start` to `end`, together with its cost. A path ends as
+    /// soon as it reaches `end`.
+    pub fn all_simple_paths(&self, start: usize, end: usize) -> Vec<(Vec<usize>, C)> {
+        let mut paths = vec![];
+        self.for_each_simple_path(start, |path, cost| {
+            if path[path.len() - 1] == end {
+                paths.push((path.to_vec(), cost));
+                Visit::Prune
+            } else {
+                Visit::Continue
+            }
+        });
+        paths
+    }
+}
+
+/// Compresses a maze into a graph of junctions. Starting from `start`, every corridor (i.e. a
+/// sequence of points that each only lead to a single next point) is followed until it ends in a
+/// junction, and is then replaced by a single edge whose cost is the length of the corridor.
+///
+/// A point is a junction if it is `start`, one of the points in `keep`, or if it has more than
+/// one neighbour besides the point it was entered from. The nodes in `keep` are added first (after
+/// `start`), so they are part of the graph even if they cannot be reached. Corridors that lead to a
+/// dead end are dropped.
+///
+/// The neighbour function may describe one-way passages, in which case the resulting graph is
+/// directed. Otherwise, every corridor results in an edge in both directions.
+pub fn compress_corridors<P, K, F, I>(start: P, keep: K, mut neighbours: F) -> Graph<P>
+where
+    P: Hash + Eq + Clone,
+    K: IntoIterator<Item = P>,
+    F: FnMut(&P) -> I,
+    I: IntoIterator<Item = P>,
+{
+    let mut graph = Graph::new();
+    graph.add_node(start.clone());
+    for point in keep {
+        graph.add_node(point);
+    }
+
+    let mut exploring = vec![start];
+    let mut explored = HashSet::new();
+    while let Some(junction) = exploring.pop() {
+        if !explored.insert(junction.clone()) {
+            continue;
+        }
+
+        for first in neighbours(&junction) {
+            let mut previous = junction.clone();
+            let mut current = first;
+            let mut length = 1;
+            let mut corridor = HashSet::new();
+
+            loop {
+                let next = neighbours(&current)
+                    .into_iter()
+                    .filter(|point| *point != previous)
+                    .collect::<Vec<P>>();
+
+                if next.len() > 1 || graph.index_of(&current).is_some() {
+                    if current != junction {
+                        graph.add_edge(junction.clone(), current.clone(), length);
+                        exploring.push(current);
+                    }
+                    break;
+                }
+
+                match next.into_iter().next() {
+                    // dead end, or a one-way loop that never reaches a junction
+                    None => break,
+                    Some(_) if !corridor.insert(current.clone()) => break,
+                    Some(point) => {
+                        previous = current;
+                        current = point;
+                        length += 1;
+                    }
+                }
+            }
+        }
+    }
+
+    graph
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Parses a maze, where `#` is a wall and anything else is open. Arrows are one-way slopes.
+    fn maze(lines: &[&str]) -> impl FnMut(&(usize, usize)) -> Vec<(usize, usize)> {
+        let cells = lines
+            .iter()
+            .map(|line| line.chars().collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+
+        move |&(r, c)| {
+            let mut neighbours = vec![];
+            let cell = cells[r][c];
+            if r > 0 && matches!(cell, '.' | '^') {
+                neighbours.push((r - 1, c));
+            }
+            if r + 1 < cells.len() && matches!(cell, '.' | 'v') {
+                neighbours.push((r + 1, c));
+            }
+            if c > 0 && matches!(cell, '.' | '<') {
+                neighbours.push((r, c - 1));
+            }
+            if c + 1 < cells[r].len() && matches!(cell, '.' | '>') {
+                neighbours.push((r, c + 1));
+            }
+            neighbours.retain(|&(r, c)| cells[r][c] != '#');
+            neighbours
+        }
+    }
+
+    fn grid_graph(size: usize) -> Graph<(usize, usize)> {
+        let mut graph = Graph::new();
+        for r in 0..size {
+            for c in 0..size {
+                if r + 1 < size {
+                    graph.add_undirected_edge((r, c), (r + 1, c), 1);
+                }
+                if c + 1 < size {
+                    graph.add_undirected_edge((r, c), (r, c + 1), 1);
+                }
+            }
+        }
+        graph
+    }
+
+    #[test]
+    fn test_graph() {
+        let mut graph: Graph<&str> = Graph::new();
+        graph.add_edge("a", "b", 3);
+        graph.add_undirected_edge("b", "c", 4);
+
+        assert_eq!(graph.len(), 3);
+        assert_eq!(graph.add_node("b"), 1);
+        assert_eq!(graph.index_of(&"c"), Some(2));
+        assert_eq!(graph.index_of(&"d"), None);
+        assert_eq!(graph.node(0), &"a");
+        assert_eq!(graph.edges(0), &[(1, 3)]);
+        assert_eq!(graph.edges(1), &[(2, 4)]);
+        assert_eq!(graph.edges(2), &[(1, 4)]);
+    }
+
+    #[test]
+    fn test_compress_corridors() {
+        let mut neighbours = maze(&[
+            "#.#####", //
+            "#.....#", //
+            "#.###.#", //
+            "#.....#", //
+            "###.###", //
+            "###.###", //
+        ]);
+        let graph = compress_corridors((0, 1), [(5, 3)], &mut neighbours);
+
+        // start, end, and the junctions at (1, 1) and (3, 3)
+        assert_eq!(graph.len(), 4);
+        let start = graph.index_of(&(0, 1)).unwrap();
+        let left = graph.index_of(&(1, 1)).unwrap();
+        let bottom = graph.index_of(&(3, 3)).unwrap();
+        let end = graph.index_of(&(5, 3)).unwrap();
+
+        assert_eq!(graph.edges(start), &[(left, 1)]);
+        let mut edges = graph.edges(left).to_vec();
+        edges.sort_unstable();
+        assert_eq!(edges, vec![(start, 1), (bottom, 4), (bottom, 8)]);
+        assert!(graph.edges(bottom).contains(&(end, 2)));
+        assert_eq!(graph.longest_path(start, end), Some(11));
+    }
+
+    #[test]
+    fn test_compress_corridors_one_way() {
+        let neighbours = maze(&[
+            "#.###", //
+            "#.>.#", //
+            "#v#.#", //
+            "#...#", //
+            "###.#", //
+        ]);
+        let graph = compress_corridors((0, 1), [(4, 3)], neighbours);
+        let start = graph.index_of(&(0, 1)).unwrap();
+        let end = graph.index_of(&(4, 3)).unwrap();
+
+        assert_eq!(graph.longest_path(start, end), Some(6));
+        assert_eq!(graph.longest_path(end, start), None);
+    }
+
+    #[test]
+    fn test_compress_corridors_drops_dead_ends() {
+        let neighbours = maze(&[
+            "#.###", //
+            "#...#", //
+            "#.#.#", //
+            "#.###", //
+        ]);
+        let graph = compress_corridors((0, 1), [(3, 1)], neighbours);
+        let start = graph.index_of(&(0, 1)).unwrap();
+        let end = graph.index_of(&(3, 1)).unwrap();
+
+        assert_eq!(graph.len(), 3);
+        assert_eq!(graph.longest_path(start, end), Some(3));
+    }
+
+    #[test]
+    fn test_longest_path() {
+        let graph = grid_graph(3);
+        let start = graph.index_of(&(0, 0)).unwrap();
+        let end = graph.index_of(&(2, 2)).unwrap();
+
+        // snake through all nine points
+        assert_eq!(graph.longest_path(start, end), Some(8));
+        assert_eq!(graph.longest_path(start, start), Some(0));
+
+        // on a 4x4 grid, opposite corners have the same colour on a checkerboard, so one point
+        // has to be skipped
+        let graph = grid_graph(4);
+        let start = graph.index_of(&(0, 0)).unwrap();
+        let end = graph.index_of(&(3, 3)).unwrap();
+        assert_eq!(graph.longest_path(start, end), Some(14));
+    }
+
+    #[test]
+    fn test_longest_path_unreachable() {
+        let mut graph: Graph<u8> = Graph::new();
+        graph.add_edge(0, 1, 5);
+        graph.add_node(2);
+
+        assert_eq!(graph.longest_path(0, 2), None);
+        assert_eq!(graph.longest_path(1, 0), None);
+    }
+
+    #[test]
+    fn test_all_simple_paths() {
+        let mut graph: Graph<char> = Graph::new();
+        graph.add_edge('a', 'b', 1);
+        graph.add_edge('a', 'c', 2);
+        graph.add_edge('b', 'c', 3);
+        graph.add_edge('c', 'd', 4);
+        graph.add_edge('b', 'd', 10);
+
+        let mut paths = graph.all_simple_paths(0, 3);
+        paths.sort_unstable();
+        assert_eq!(
+            paths,
+            vec![
+                (vec![0, 1, 2, 3], 8),
+                (vec![0, 1, 3], 11),
+                (vec![0, 2, 3], 6),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_for_each_simple_path_with_pruning() {
+        let graph = grid_graph(3);
+        let start = graph.index_of(&(0, 0)).unwrap();
+
+        let mut count = 0;
+        let mut longest = 0;
+        graph.for_each_simple_path(start, |path, cost| {
+            count += 1;
+            longest = longest.max(path.len());
+            if cost < 2 {
+                Visit::Continue
+            } else {
+                Visit::Prune
+            }
+        });
+
+        // paths of 0, 1 and 2 steps from a corner: 1 + 2 + 4
+        assert_eq!(count, 7);
+        assert_eq!(longest, 3);
+    }
+}
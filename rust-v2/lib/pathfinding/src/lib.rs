@@ -6,6 +6,19 @@ use std::collections::{BinaryHeap, HashMap};
 use std::hash::Hash;
 use std::ops::{Add, Sub};
 
+use crate::cost::Cost;
+use crate::observe::{SearchEvent, SearchObserver};
+use crate::search::{reconstruct_path, Path};
+
+pub mod branch_and_bound;
+pub mod connectivity;
+pub mod cost;
+pub mod distance;
+pub mod graph;
+pub mod observe;
+pub mod search;
+mod variants;
+
 /// The absolute difference between two numbers.
 pub fn abs_diff<T>(a: T, b: T) -> T
 where
@@ -42,17 +55,34 @@ where
 }
 
 /// Use [A*](https://en.wikipedia.org/wiki/A*_search_algorithm) to find the shortest path between two points.
-pub trait AStar {
+///
+/// The distances between points are `u64`s, unless another type of [`Cost`] is specified (e.g.
+/// `impl AStar<u32> for ...`). Distances must never be negative, and the searches panic if they
+/// find a negative distance between neighbours. Paths whose cost does not fit in the cost type
+/// are ignored.
+pub trait AStar<C: Cost = u64> {
     /// The representation of a point, usually some coordinates.
     type Point;
     type EndPoint;
 
     /// A heuristic estimate for the distance between two points.
     /// Should always return a value that is no larger than the actual distance.
-    fn distance_score(&self, a: &Self::Point, b: &Self::EndPoint) -> u64;
+    fn distance_score(&self, a: &Self::Point, b: &Self::EndPoint) -> C;
 
     /// Returns the points that can be reached directly from `point`, together with the distance.
-    fn get_neighbours(&self, point: &Self::Point) -> Vec<(u64, Self::Point)>;
+    fn get_neighbours(&self, point: &Self::Point) -> Vec<(C, Self::Point)>;
+
+    /// Returns the points from which `point` can be reached directly. This is only used by
+    /// [`find_path_bidirectional`](AStar::find_path_bidirectional), to search backwards from the
+    /// end. The default implementation assumes that the neighbour relation is symmetric (i.e. if
+    /// `b` is a neighbour of `a`, then `a` is a neighbour of `b`), so it must be overridden for
+    /// directed graphs.
+    fn get_predecessors(&self, point: &Self::Point) -> Vec<Self::Point> {
+        self.get_neighbours(point)
+            .into_iter()
+            .map(|(_, neighbour)| neighbour)
+            .collect()
+    }
 
     /// Find the shortest path between two points.
     fn find_shortest_path(
@@ -63,42 +93,137 @@ pub trait AStar {
     where
         Self::Point: Hash + PartialEq + Eq + PartialEq<Self::EndPoint> + Clone,
     {
-        let mut open_set: BinaryHeap<SortablePoint<Self::Point>> = BinaryHeap::new();
+        self.find_path(start, end).map(|path| path.points)
+    }
+
+    /// Find the shortest path between two points, together with its total cost.
+    fn find_path(&self, start: &Self::Point, end: &Self::EndPoint) -> Option<Path<Self::Point, C>>
+    where
+        Self::Point: Hash + PartialEq + Eq + PartialEq<Self::EndPoint> + Clone,
+    {
+        self.find_path_where(start, end, |point| point == end, None)
+    }
+
+    /// Find the shortest path from `start` to any point that satisfies `is_goal`. The `end` point
+    /// is only used to compute the heuristic, so `distance_score` must never overestimate the
+    /// distance to any of the goals.
+    ///
+    /// If `max_cost` is specified, the search is aborted as soon as it becomes clear that no path
+    /// exists with a cost of at most `max_cost`.
+    fn find_path_where<G>(
+        &self,
+        start: &Self::Point,
+        end: &Self::EndPoint,
+        is_goal: G,
+        max_cost: Option<C>,
+    ) -> Option<Path<Self::Point, C>>
+    where
+        Self::Point: Hash + Eq + Clone,
+        G: Fn(&Self::Point) -> bool,
+    {
+        self.find_path_observed(start, end, is_goal, max_cost, &mut |_: SearchEvent<
+            _,
+            _,
+        >| {})
+    }
+
+    /// Like [`find_path_where`](AStar::find_path_where), but reports everything the search does to
+    /// `observer`. This is meant for debugging slow searches, see [`SearchStats`](observe::SearchStats)
+    /// for a ready-made observer.
+    fn find_path_observed<G, O>(
+        &self,
+        start: &Self::Point,
+        end: &Self::EndPoint,
+        is_goal: G,
+        max_cost: Option<C>,
+        observer: &mut O,
+    ) -> Option<Path<Self::Point, C>>
+    where
+        Self::Point: Hash + Eq + Clone,
+        G: Fn(&Self::Point) -> bool,
+        O: SearchObserver<Self::Point, C> + ?Sized,
+    {
+        let exceeds_max_cost = |cost: C| max_cost.is_some_and(|max| cost > max);
+        let mut expanded = 0;
+
+        let mut open_set: BinaryHeap<SortablePoint<Self::Point, C>> = BinaryHeap::new();
         open_set.push(SortablePoint {
             point: start.clone(),
             f_score: self.distance_score(start, end),
         });
+        observer.observe(SearchEvent::Push {
+            point: start,
+            cost: C::ZERO,
+            f_score: self.distance_score(start, end),
+            open_set: open_set.len(),
+        });
 
         let mut came_from: HashMap<Self::Point, Self::Point> = HashMap::new();
 
-        let mut g_score: HashMap<Self::Point, u64> = HashMap::new();
-        g_score.insert(start.clone(), 0);
+        let mut g_score: HashMap<Self::Point, C> = HashMap::new();
+        g_score.insert(start.clone(), C::ZERO);
 
         while let Some(current) = open_set.pop() {
-            let current = &current.point;
-            if current == end {
-                let mut point = current;
-                let mut path = vec![point.clone()];
-                while let Some(p) = came_from.get(point) {
-                    point = p;
-                    path.push(p.clone());
-                }
-                path.reverse();
-                return Some(path);
+            if exceeds_max_cost(current.f_score) {
+                return None;
             }
 
-            let current_distance = *g_score.get(current).unwrap_or(&u64::MAX);
-
-            for (d, neighbour) in &self.get_neighbours(current) {
-                let distance = current_distance + d;
-                let neighbour_distance = *g_score.get(neighbour).unwrap_or(&u64::MAX);
+            let f_score = current.f_score;
+            let current = &current.point;
+            let current_distance = g_score[current];
+            if is_goal(current) {
+                return Some(Path {
+                    cost: current_distance,
+                    points: reconstruct_path(&came_from, current),
+                    expanded,
+                });
+            }
+            let is_stale = current_distance
+                .checked_add(self.distance_score(current, end))
+                .is_none_or(|f| f_score > f);
+            if is_stale {
+                // a shorter path to this point was found after it was added to the open set
+                continue;
+            }
 
-                if distance < neighbour_distance {
+            expanded += 1;
+            observer.observe(SearchEvent::Pop {
+                point: current,
+                cost: current_distance,
+                f_score,
+                open_set: open_set.len(),
+            });
+            for (d, neighbour) in self.get_neighbours(current) {
+                // a path that does not fit in the cost type is never the shortest path
+                let Some(distance) = cost::extend(current_distance, d) else {
+                    continue;
+                };
+                let Some(f_score) = distance.checked_add(self.distance_score(&neighbour, end))
+                else {
+                    continue;
+                };
+
+                let previous = g_score.get(&neighbour).copied();
+                let is_shorter = previous.is_none_or(|g| distance < g);
+                if is_shorter && !exceeds_max_cost(distance) {
+                    if previous.is_some() {
+                        observer.observe(SearchEvent::Relax {
+                            point: &neighbour,
+                            from: current,
+                            cost: distance,
+                        });
+                    }
                     came_from.insert(neighbour.clone(), current.clone());
                     g_score.insert(neighbour.clone(), distance);
+                    observer.observe(SearchEvent::Push {
+                        point: &neighbour,
+                        cost: distance,
+                        f_score,
+                        open_set: open_set.len() + 1,
+                    });
                     open_set.push(SortablePoint {
-                        point: neighbour.clone(),
-                        f_score: distance.add(self.distance_score(neighbour, end)),
+                        point: neighbour,
+                        f_score,
                     });
                 }
             }
@@ -106,30 +231,73 @@ pub trait AStar {
 
         None
     }
+
+    /// Find the shortest path from `start` to any point that satisfies `is_goal`, using
+    /// [IDA*](https://en.wikipedia.org/wiki/Iterative_deepening_A*). The parameters have the same
+    /// meaning as in [`find_path_where`](AStar::find_path_where).
+    ///
+    /// Instead of keeping track of every point that was reached, IDA* repeatedly performs a depth
+    /// first search that is bounded by the f-score, raising the bound after every iteration. Only
+    /// the current path is kept in memory, which makes this suitable for huge state spaces. The
+    /// downside is that points are visited many times, especially if there are many different
+    /// paths to the same point.
+    fn find_path_ida<G>(
+        &self,
+        start: &Self::Point,
+        end: &Self::EndPoint,
+        is_goal: G,
+        max_cost: Option<C>,
+    ) -> Option<Path<Self::Point, C>>
+    where
+        Self::Point: Hash + Eq + Clone,
+        G: Fn(&Self::Point) -> bool,
+    {
+        variants::ida_star(self, start, end, is_goal, max_cost)
+    }
+
+    /// Find the path with the fewest steps between two points, by searching from both ends at the
+    /// same time until the searches meet. The costs returned by `get_neighbours` and the
+    /// heuristic are ignored, and the cost of the resulting path is its number of steps.
+    ///
+    /// The search from the end follows [`get_predecessors`](AStar::get_predecessors), which must
+    /// be overridden if the neighbour relation is not symmetric. Every point that is reached is
+    /// stored together with the point from which it was reached, just like in a regular search.
+    /// The saving comes from the explored region: two searches that each cover half the distance
+    /// usually reach far fewer points than a single search that covers all of it.
+    fn find_path_bidirectional(
+        &self,
+        start: &Self::Point,
+        end: &Self::Point,
+    ) -> Option<Path<Self::Point>>
+    where
+        Self::Point: Hash + Eq + Clone,
+    {
+        variants::bidirectional_bfs(self, start, end)
+    }
 }
 
 /// In the A* implementation, a priority queue (i.e. BinaryHeap) is used. This wrapper around the
 /// points allows sorting on the f_score. Note that a descending sort is used.
-struct SortablePoint<P> {
+struct SortablePoint<P, C> {
     point: P,
-    f_score: u64,
+    f_score: C,
 }
 
-impl<P> PartialEq<Self> for SortablePoint<P> {
+impl<P, C: Ord> PartialEq<Self> for SortablePoint<P, C> {
     fn eq(&self, other: &Self) -> bool {
         other.f_score.eq(&self.f_score)
     }
 }
 
-impl<P> Eq for SortablePoint<P> {}
+impl<P, C: Ord> Eq for SortablePoint<P, C> {}
 
-impl<P> PartialOrd<Self> for SortablePoint<P> {
+impl<P, C: Ord> PartialOrd<Self> for SortablePoint<P, C> {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         Some(self.cmp(other))
     }
 }
 
-impl<P> Ord for SortablePoint<P> {
+impl<P, C: Ord> Ord for SortablePoint<P, C> {
     fn cmp(&self, other: &Self) -> Ordering {
         other.f_score.cmp(&self.f_score)
     }
@@ -277,6 +445,285 @@ mod tests {
             assert_eq!(path, None);
         }
 
+        #[test]
+        fn test_find_path() {
+            let nav = TestNav::new(10, 5, &[]);
+            let path = nav.find_path(&(1, 1), &(10, 5)).unwrap();
+
+            assert_eq!(path.cost, 13);
+            assert_eq!(path.points.len(), 14);
+            assert!(path.expanded >= 13);
+        }
+
+        #[test]
+        fn test_find_path_where() {
+            let nav = TestNav::new(10, 5, &[]);
+            let path = nav
+                .find_path_where(&(1, 1), &(10, 1), |&(x, _)| x == 10, None)
+                .unwrap();
+
+            assert_eq!(path.cost, 9);
+            assert_eq!(path.points.last(), Some(&(10, 1)));
+        }
+
+        #[test]
+        fn test_find_path_where_with_max_cost() {
+            let nav = TestNav::new(10, 5, &[]);
+
+            let path = nav.find_path_where(&(1, 1), &(10, 5), |p| *p == (10, 5), Some(13));
+            assert_eq!(path.map(|p| p.cost), Some(13));
+
+            let path = nav.find_path_where(&(1, 1), &(10, 5), |p| *p == (10, 5), Some(12));
+            assert_eq!(path, None);
+        }
+
+        #[test]
+        fn test_find_path_with_other_cost_type() {
+            let path = Line.find_path(&0, &10).unwrap();
+            assert_eq!(path.cost, 46);
+            assert_eq!(path.points, (0..=10).collect::<Vec<_>>());
+
+            let path = Line.find_path_ida(&0, &10, |&p| p == 10, None).unwrap();
+            assert_eq!(path.cost, 46);
+        }
+
+        #[test]
+        fn test_find_path_overflow() {
+            // the cost of reaching 30 does not fit in a u8
+            assert!(Line.find_path(&0, &30).is_none());
+            assert!(Line.find_path_ida(&0, &30, |&p| p == 30, None).is_none());
+        }
+
+        #[test]
+        fn test_find_path_skips_overflowing_paths() {
+            // the path to 3 via 1 does not fit in a u8, but the path via 2 does
+            let path = Diamond.find_path(&0, &4).unwrap();
+            assert_eq!(path.cost, 252);
+            assert_eq!(path.points, vec![0, 2, 3, 4]);
+
+            let path = Diamond.find_path_ida(&0, &4, |&p| p == 4, None).unwrap();
+            assert_eq!(path.cost, 252);
+            assert_eq!(path.points, vec![0, 2, 3, 4]);
+        }
+
+        struct Diamond;
+
+        impl AStar<u8> for Diamond {
+            type Point = u8;
+            type EndPoint = u8;
+
+            fn distance_score(&self, _: &u8, _: &u8) -> u8 {
+                0
+            }
+
+            fn get_neighbours(&self, &point: &u8) -> Vec<(u8, u8)> {
+                match point {
+                    0 => vec![(200, 1), (1, 2)],
+                    1 => vec![(100, 3)],
+                    2 => vec![(1, 3)],
+                    3 => vec![(250, 4)],
+                    _ => vec![],
+                }
+            }
+        }
+
+        struct Line;
+
+        impl AStar<u8> for Line {
+            type Point = u8;
+            type EndPoint = u8;
+
+            fn distance_score(&self, _: &u8, _: &u8) -> u8 {
+                0
+            }
+
+            fn get_neighbours(&self, &point: &u8) -> Vec<(u8, u8)> {
+                // stepping backwards is free, stepping forwards costs more the further you go
+                let mut neighbours = vec![];
+                if point > 0 {
+                    neighbours.push((0, point - 1));
+                }
+                if point < 30 {
+                    neighbours.push((point.max(1), point + 1));
+                }
+                neighbours
+            }
+        }
+
+        #[test]
+        fn test_find_path_observed() {
+            let nav = TestNav::new(10, 5, &[(2, 1), (2, 2), (2, 3)]);
+            let mut stats = observe::SearchStats::new();
+            let path = nav
+                .find_path_observed(&(1, 1), &(10, 5), |p| *p == (10, 5), None, &mut stats)
+                .unwrap();
+
+            assert_eq!(path.cost, 13);
+            assert_eq!(stats.expanded, path.expanded);
+            assert_eq!(stats.explored().len(), path.expanded);
+            assert!(stats.pushed > stats.expanded);
+            assert!(stats.open_set_peak > 0);
+        }
+
+        #[test]
+        fn test_find_path_ida() {
+            let obstacles = vec![
+                (2, 1),
+                (2, 2),
+                (2, 3),
+                (2, 4),
+                (4, 2),
+                (4, 3),
+                (4, 4),
+                (4, 5),
+            ];
+            let nav = TestNav::new(5, 5, &obstacles);
+
+            let path = nav
+                .find_path_ida(&(1, 1), &(5, 5), |p| *p == (5, 5), None)
+                .unwrap();
+            let expected = nav.find_path(&(1, 1), &(5, 5)).unwrap();
+            assert_eq!(path.cost, expected.cost);
+            assert_eq!(path.points.len(), expected.points.len());
+
+            let path = nav.find_path_ida(&(1, 1), &(5, 5), |p| *p == (5, 5), Some(15));
+            assert_eq!(path, None);
+        }
+
+        #[test]
+        fn test_find_path_ida_impossible() {
+            let nav = TestNav::new(3, 3, &[(2, 1), (2, 2), (2, 3)]);
+            let path = nav.find_path_ida(&(1, 1), &(3, 3), |p| *p == (3, 3), None);
+            assert_eq!(path, None);
+        }
+
+        #[test]
+        fn test_find_path_bidirectional() {
+            let obstacles = vec![
+                (2, 1),
+                (2, 2),
+                (2, 3),
+                (2, 4),
+                (4, 2),
+                (4, 3),
+                (4, 4),
+                (4, 5),
+            ];
+            let nav = TestNav::new(5, 5, &obstacles);
+
+            let path = nav.find_path_bidirectional(&(1, 1), &(5, 5)).unwrap();
+            assert_eq!(path.cost, 16);
+            assert_eq!(path.points.len(), 17);
+            assert_eq!(path.points.first(), Some(&(1, 1)));
+            assert_eq!(path.points.last(), Some(&(5, 5)));
+            for step in path.points.windows(2) {
+                assert_eq!(taxi_cab_2d(step[0], step[1]), 1);
+            }
+
+            let path = nav.find_path_bidirectional(&(3, 3), &(3, 3)).unwrap();
+            assert_eq!(path.points, vec![(3, 3)]);
+        }
+
+        #[test]
+        fn test_find_path_bidirectional_matches_astar() {
+            let obstacles = vec![
+                (2, 2),
+                (2, 3),
+                (3, 2),
+                (5, 1),
+                (5, 2),
+                (5, 3),
+                (5, 4),
+                (7, 5),
+            ];
+            let nav = TestNav::new(8, 6, &obstacles);
+            let points = (1..=8)
+                .flat_map(|x| (1..=6).map(move |y| (x, y)))
+                .filter(|p| !obstacles.contains(p))
+                .collect::<Vec<_>>();
+
+            for start in &points {
+                for end in &points {
+                    let path = nav.find_path_bidirectional(start, end).unwrap();
+                    let expected = nav.find_path(start, end).unwrap();
+                    assert_eq!(path.cost, expected.cost);
+                    assert_eq!(path.points.len() as u64, path.cost + 1);
+                    assert_eq!(path.points.first(), Some(start));
+                    assert_eq!(path.points.last(), Some(end));
+                    for step in path.points.windows(2) {
+                        assert_eq!(taxi_cab_2d(step[0], step[1]), 1);
+                    }
+                }
+            }
+        }
+
+        #[test]
+        fn test_find_path_bidirectional_directed() {
+            struct OneWay;
+
+            impl AStar for OneWay {
+                type Point = u8;
+                type EndPoint = u8;
+
+                fn distance_score(&self, _: &u8, _: &u8) -> u64 {
+                    0
+                }
+
+                fn get_neighbours(&self, &point: &u8) -> Vec<(u64, u8)> {
+                    if point < 3 {
+                        vec![(1, point + 1)]
+                    } else {
+                        vec![]
+                    }
+                }
+
+                fn get_predecessors(&self, &point: &u8) -> Vec<u8> {
+                    if point > 0 {
+                        vec![point - 1]
+                    } else {
+                        vec![]
+                    }
+                }
+            }
+
+            let path = OneWay.find_path_bidirectional(&0, &3).unwrap();
+            assert_eq!(path.cost, 3);
+            assert_eq!(path.points, vec![0, 1, 2, 3]);
+            assert_eq!(OneWay.find_path_bidirectional(&3, &0), None);
+        }
+
+        #[test]
+        fn test_find_path_bidirectional_directed_impossible() {
+            // two separate cycles: 0 -> 1 -> 2 -> 0 and 3 -> 4 -> 5 -> 3
+            struct Cycles;
+
+            impl AStar for Cycles {
+                type Point = u8;
+                type EndPoint = u8;
+
+                fn distance_score(&self, _: &u8, _: &u8) -> u64 {
+                    0
+                }
+
+                fn get_neighbours(&self, &point: &u8) -> Vec<(u64, u8)> {
+                    vec![(1, point / 3 * 3 + (point + 1) % 3)]
+                }
+
+                fn get_predecessors(&self, &point: &u8) -> Vec<u8> {
+                    vec![point / 3 * 3 + (point + 2) % 3]
+                }
+            }
+
+            assert_eq!(Cycles.find_path_bidirectional(&0, &3), None);
+            assert_eq!(Cycles.find_path_bidirectional(&0, &2).unwrap().cost, 2);
+        }
+
+        #[test]
+        fn test_find_path_bidirectional_impossible() {
+            let nav = TestNav::new(3, 3, &[(2, 1), (2, 2), (2, 3)]);
+            assert_eq!(nav.find_path_bidirectional(&(1, 1), &(3, 3)), None);
+        }
+
         struct TestNav {
             width: u64,
             height: u64,
@@ -0,0 +1,305 @@
+//! Hooks to look inside a running [`AStar`] search, which helps to find out why a search is slow.
+//!
+//! Pass an observer to [`AStar::find_path_observed`] to receive a [`SearchEvent`] for everything
+//! the search does. Any closure that accepts a `SearchEvent` is an observer, and [`SearchStats`]
+//! is a ready-made observer that collects statistics and can render the explored region of a grid.
+//!
+//! # Usage
+//!
+//! ```
+//! use rdcl_aoc_pathfinding::observe::SearchStats;
+//! use rdcl_aoc_pathfinding::AStar;
+//!
+//! struct Open;
+//!
+//! impl AStar<usize> for Open {
+//!     type Point = (usize, usize);
+//!     type EndPoint = (usize, usize);
+//!
+//!     fn distance_score(&self, a: &(usize, usize), b: &(usize, usize)) -> usize {
+//!         a.0.abs_diff(b.0) + a.1.abs_diff(b.1)
+//!     }
+//!
+//!     fn get_neighbours(&self, &(row, col): &(usize, usize)) -> Vec<(usize, (usize, usize))> {
+//!         [(row + 1, col), (row, col + 1)]
+//!             .into_iter()
+//!             .filter(|&(row, col)| row < 2 && col < 5)
+//!             .map(|p| (1, p))
+//!             .collect()
+//!     }
+//! }
+//!
+//! let mut stats = SearchStats::new().with_frames(2, (2, 5), |&p| Some(p));
+//! let path = Open.find_path_observed(&(0, 0), &(0, 4), |&p| p == (0, 4), None, &mut stats);
+//!
+//! assert_eq!(path.unwrap().cost, 4);
+//! assert_eq!(stats.expanded, 4);
+//! assert_eq!(stats.frames().len(), 2);
+//! assert_eq!(stats.render((2, 5), |&p| Some(p)), "####+\n++++.");
+//! ```
+//!
+//! [`AStar`]: crate::AStar
+//! [`AStar::find_path_observed`]: crate::AStar::find_path_observed
+
+use std::collections::HashSet;
+use std::fmt;
+use std::hash::Hash;
+
+/// Something that happened during a search.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum SearchEvent<'a, P, C> {
+    /// A point was added to the open set.
+    Push {
+        /// The point that was added.
+        point: &'a P,
+        /// The cost of the best known path to the point.
+        cost: C,
+        /// The cost of the path plus the heuristic.
+        f_score: C,
+        /// The size of the open set, after the point was added.
+        open_set: usize,
+    },
+    /// A point was taken from the open set, and its neighbours are about to be examined. Entries
+    /// in the open set that have become obsolete (because a shorter path to the point was found
+    /// afterwards) are discarded without an event.
+    Pop {
+        /// The point that was taken from the open set.
+        point: &'a P,
+        /// The cost of the shortest path to the point.
+        cost: C,
+        /// The cost of the path plus the heuristic.
+        f_score: C,
+        /// The size of the open set, after the point was taken from it.
+        open_set: usize,
+    },
+    /// A shorter path was found to a point that had already been reached before. This event is
+    /// followed by a [`Push`](SearchEvent::Push) event for the same point.
+    Relax {
+        /// The point to which a shorter path was found.
+        point: &'a P,
+        /// The point that precedes `point` on the shorter path.
+        from: &'a P,
+        /// The cost of the shorter path.
+        cost: C,
+    },
+}
+
+/// Receives the events of a search.
+pub trait SearchObserver<P, C> {
+    /// Called for every event.
+    fn observe(&mut self, event: SearchEvent<'_, P, C>);
+}
+
+impl<P, C, F> SearchObserver<P, C> for F
+where
+    F: FnMut(SearchEvent<'_, P, C>),
+{
+    fn observe(&mut self, event: SearchEvent<'_, P, C>) {
+        self(event)
+    }
+}
+
+type Position<P> = Box<dyn Fn(&P) -> Option<(usize, usize)>>;
+
+/// An observer that collects statistics about a search. It can also take snapshots of the
+/// explored region, if the points can be mapped onto a grid (see [`with_frames`]).
+///
+/// In the snapshots, a `#` marks a point that was expanded, a `+` marks a point that is still in
+/// the open set, and a `.` marks a point that has not been reached (yet).
+///
+/// [`with_frames`]: SearchStats::with_frames
+pub struct SearchStats<P> {
+    /// The number of points that were expanded.
+    pub expanded: usize,
+    /// The number of points that were added to the open set.
+    pub pushed: usize,
+    /// The number of times a shorter path was found to a point that had already been reached.
+    pub relaxed: usize,
+    /// The largest size of the open set during the search.
+    pub open_set_peak: usize,
+    explored: HashSet<P>,
+    open: HashSet<P>,
+    frames: Vec<String>,
+    recorder: Option<(usize, (usize, usize), Position<P>)>,
+}
+
+impl<P> SearchStats<P>
+where
+    P: Hash + Eq + Clone,
+{
+    /// Creates an observer that does not take any snapshots.
+    pub fn new() -> SearchStats<P> {
+        SearchStats {
+            expanded: 0,
+            pushed: 0,
+            relaxed: 0,
+            open_set_peak: 0,
+            explored: HashSet::new(),
+            open: HashSet::new(),
+            frames: vec![],
+            recorder: None,
+        }
+    }
+
+    /// Takes a snapshot after every `every` expanded points. The snapshot is a grid of `size`
+    /// (as `(rows, cols)`), and `position` maps points onto that grid. Points for which `position`
+    /// returns `None` are not shown.
+    pub fn with_frames<F>(mut self, every: usize, size: (usize, usize), position: F) -> Self
+    where
+        F: Fn(&P) -> Option<(usize, usize)> + 'static,
+    {
+        self.recorder = Some((every.max(1), size, Box::new(position)));
+        self
+    }
+
+    /// All points that were expanded.
+    pub fn explored(&self) -> &HashSet<P> {
+        &self.explored
+    }
+
+    /// The snapshots that were taken.
+    pub fn frames(&self) -> &[String] {
+        &self.frames
+    }
+
+    /// Renders the current state of the search as a grid of `size` (as `(rows, cols)`), where
+    /// `position` maps points onto the grid.
+    pub fn render<F>(&self, size: (usize, usize), position: F) -> String
+    where
+        F: Fn(&P) -> Option<(usize, usize)>,
+    {
+        let (rows, cols) = size;
+        let mut cells = vec![vec!['.'; cols]; rows];
+        let mut mark = |points: &HashSet<P>, ch: char| {
+            for point in points {
+                if let Some((row, col)) = position(point) {
+                    if row < rows && col < cols {
+                        cells[row][col] = ch;
+                    }
+                }
+            }
+        };
+        mark(&self.open, '+');
+        mark(&self.explored, '#');
+
+        cells
+            .into_iter()
+            .map(|row| row.into_iter().collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+impl<P> Default for SearchStats<P>
+where
+    P: Hash + Eq + Clone,
+{
+    fn default() -> Self {
+        SearchStats::new()
+    }
+}
+
+impl<P> fmt::Debug for SearchStats<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SearchStats")
+            .field("expanded", &self.expanded)
+            .field("pushed", &self.pushed)
+            .field("relaxed", &self.relaxed)
+            .field("open_set_peak", &self.open_set_peak)
+            .finish()
+    }
+}
+
+impl<P, C> SearchObserver<P, C> for SearchStats<P>
+where
+    P: Hash + Eq + Clone,
+{
+    fn observe(&mut self, event: SearchEvent<'_, P, C>) {
+        match event {
+            SearchEvent::Push {
+                point, open_set, ..
+            } => {
+                self.pushed += 1;
+                self.open_set_peak = self.open_set_peak.max(open_set);
+                if !self.explored.contains(point) {
+                    self.open.insert(point.clone());
+                }
+            }
+            SearchEvent::Pop { point, .. } => {
+                self.expanded += 1;
+                self.open.remove(point);
+                self.explored.insert(point.clone());
+
+                if let Some((every, size, position)) = &self.recorder {
+                    if self.expanded.is_multiple_of(*every) {
+                        let frame = self.render(*size, position);
+                        self.frames.push(frame);
+                    }
+                }
+            }
+            SearchEvent::Relax { .. } => {
+                self.relaxed += 1;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_search_stats() {
+        let mut stats: SearchStats<(usize, usize)> = SearchStats::new();
+        stats.observe(SearchEvent::Push {
+            point: &(0, 0),
+            cost: 0,
+            f_score: 2,
+            open_set: 1,
+        });
+        stats.observe(SearchEvent::Pop {
+            point: &(0, 0),
+            cost: 0,
+            f_score: 2,
+            open_set: 0,
+        });
+        for (point, open_set) in [((0, 1), 1), ((1, 0), 2)] {
+            stats.observe(SearchEvent::Push {
+                point: &point,
+                cost: 1,
+                f_score: 2,
+                open_set,
+            });
+        }
+        stats.observe(SearchEvent::Relax {
+            point: &(1, 0),
+            from: &(0, 0),
+            cost: 1,
+        });
+
+        assert_eq!(stats.expanded, 1);
+        assert_eq!(stats.pushed, 3);
+        assert_eq!(stats.relaxed, 1);
+        assert_eq!(stats.open_set_peak, 2);
+        assert!(stats.explored().contains(&(0, 0)));
+        assert_eq!(stats.render((2, 3), |&p| Some(p)), "#+.\n+..");
+    }
+
+    #[test]
+    fn test_closure_observer() {
+        let mut pops = vec![];
+        let mut observer = |event: SearchEvent<'_, u32, u32>| {
+            if let SearchEvent::Pop { point, .. } = event {
+                pops.push(*point);
+            }
+        };
+        observer.observe(SearchEvent::Pop {
+            point: &7,
+            cost: 0,
+            f_score: 0,
+            open_set: 0,
+        });
+
+        assert_eq!(pops, vec![7]);
+    }
+}
@@ -0,0 +1,499 @@
+//! Uninformed searches, which explore a graph outwards from one or more sources.
+//!
+//! The graph is described by a function which returns the neighbours of a point, just like
+//! [`AStar::get_neighbours`]. For [`bfs`] and [`bfs_all`] every step has cost 1, so the neighbour
+//! function only returns points. For [`dijkstra`] and [`dijkstra_all`] the neighbour function
+//! returns `(cost, point)` pairs, where the cost can be of any type that implements [`Cost`]. Paths
+//! whose cost overflows are ignored, and a negative cost causes a panic.
+//!
+//! If the graph is already described by an implementation of [`AStar`], the `_from` variants
+//! (e.g. [`dijkstra_all_from`]) use its [`get_neighbours`](AStar::get_neighbours) instead.
+//!
+//! # Usage
+//!
+//! ```
+//! use rdcl_aoc_pathfinding::search::{bfs, dijkstra_all};
+//!
+//! // a line of points 0..10, where you can step forwards by 1 or 3
+//! let neighbours = |p: &u32| [p + 1, p + 3].into_iter().filter(|&n| n < 10);
+//!
+//! let path = bfs([0], neighbours, |&p| p == 9).unwrap();
+//! assert_eq!(path.cost, 3);
+//! assert_eq!(path.points, vec![0, 3, 6, 9]);
+//!
+//! // stepping forwards by 3 is expensive
+//! let distances = dijkstra_all([0], |p: &u32| {
+//!     [(1, p + 1), (5, p + 3)].into_iter().filter(|&(_, n)| n < 10)
+//! });
+//! assert_eq!(distances.distance(&9), Some(9));
+//! assert_eq!(distances.path_to(&3).unwrap().points, vec![0, 1, 2, 3]);
+//! ```
+
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+
+use crate::cost::Cost;
+use crate::{AStar, SortablePoint};
+
+/// A path that was found by a search.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Path<P, C = u64> {
+    /// The total cost of the path.
+    pub cost: C,
+    /// The points on the path, starting with the source and ending with the goal.
+    pub points: Vec<P>,
+    /// The number of points that were expanded (i.e. of which the neighbours were examined)
+    /// during the search.
+    pub expanded: usize,
+}
+
+/// The distances from the sources of a search to every point that was reached, together with the
+/// predecessor of every point on its shortest path.
+#[derive(Debug, Clone)]
+pub struct DistanceMap<P, C = u64> {
+    distances: HashMap<P, C>,
+    predecessors: HashMap<P, P>,
+    expanded: usize,
+}
+
+impl<P, C> DistanceMap<P, C>
+where
+    P: Hash + Eq + Clone,
+    C: Cost,
+{
+    fn new() -> DistanceMap<P, C> {
+        DistanceMap {
+            distances: HashMap::new(),
+            predecessors: HashMap::new(),
+            expanded: 0,
+        }
+    }
+
+    /// The distance to `point`, or `None` if it was not reached.
+    pub fn distance(&self, point: &P) -> Option<C> {
+        self.distances.get(point).copied()
+    }
+
+    /// All points that were reached, together with their distances.
+    pub fn distances(&self) -> &HashMap<P, C> {
+        &self.distances
+    }
+
+    /// The point that precedes `point` on its shortest path. Returns `None` for the sources, and
+    /// for points that were not reached.
+    pub fn predecessor(&self, point: &P) -> Option<&P> {
+        self.predecessors.get(point)
+    }
+
+    /// The predecessors of all points that were reached, except for the sources.
+    pub fn predecessors(&self) -> &HashMap<P, P> {
+        &self.predecessors
+    }
+
+    /// Whether `point` was reached.
+    pub fn contains(&self, point: &P) -> bool {
+        self.distances.contains_key(point)
+    }
+
+    /// The number of points that were reached.
+    pub fn len(&self) -> usize {
+        self.distances.len()
+    }
+
+    /// Whether no points were reached at all.
+    pub fn is_empty(&self) -> bool {
+        self.distances.is_empty()
+    }
+
+    /// Reconstructs the shortest path from one of the sources to `point`.
+    pub fn path_to(&self, point: &P) -> Option<Path<P, C>> {
+        let cost = self.distance(point)?;
+        let points = reconstruct_path(&self.predecessors, point);
+
+        Some(Path {
+            cost,
+            points,
+            expanded: self.expanded,
+        })
+    }
+}
+
+/// Use a [breadth-first search](https://en.wikipedia.org/wiki/Breadth-first_search) to find the
+/// shortest path from any of the sources to a point that satisfies `is_goal`.
+pub fn bfs<P, S, F, N, G>(sources: S, neighbours: F, is_goal: G) -> Option<Path<P>>
+where
+    P: Hash + Eq + Clone,
+    S: IntoIterator<Item = P>,
+    F: FnMut(&P) -> N,
+    N: IntoIterator<Item = P>,
+    G: FnMut(&P) -> bool,
+{
+    let (distances, goal) = run_bfs(sources, neighbours, Some(is_goal));
+    distances.path_to(&goal?)
+}
+
+/// Use a [breadth-first search](https://en.wikipedia.org/wiki/Breadth-first_search) to compute
+/// the distance from the nearest source to every reachable point.
+pub fn bfs_all<P, S, F, N>(sources: S, neighbours: F) -> DistanceMap<P>
+where
+    P: Hash + Eq + Clone,
+    S: IntoIterator<Item = P>,
+    F: FnMut(&P) -> N,
+    N: IntoIterator<Item = P>,
+{
+    run_bfs(sources, neighbours, None::<fn(&P) -> bool>).0
+}
+
+/// Use [Dijkstra's algorithm](https://en.wikipedia.org/wiki/Dijkstra%27s_algorithm) to find the
+/// cheapest path from any of the sources to a point that satisfies `is_goal`.
+pub fn dijkstra<P, C, S, F, N, G>(sources: S, neighbours: F, is_goal: G) -> Option<Path<P, C>>
+where
+    P: Hash + Eq + Clone,
+    C: Cost,
+    S: IntoIterator<Item = P>,
+    F: FnMut(&P) -> N,
+    N: IntoIterator<Item = (C, P)>,
+    G: FnMut(&P) -> bool,
+{
+    let (distances, goal) = run_dijkstra(sources, neighbours, Some(is_goal));
+    distances.path_to(&goal?)
+}
+
+/// Use [Dijkstra's algorithm](https://en.wikipedia.org/wiki/Dijkstra%27s_algorithm) to compute
+/// the cost of the cheapest path from any of the sources to every reachable point.
+pub fn dijkstra_all<P, C, S, F, N>(sources: S, neighbours: F) -> DistanceMap<P, C>
+where
+    P: Hash + Eq + Clone,
+    C: Cost,
+    S: IntoIterator<Item = P>,
+    F: FnMut(&P) -> N,
+    N: IntoIterator<Item = (C, P)>,
+{
+    run_dijkstra(sources, neighbours, None::<fn(&P) -> bool>).0
+}
+
+/// Like [`bfs`], but uses the neighbours of an [`AStar`] implementation. The distances between
+/// neighbours are ignored, every step has cost 1.
+pub fn bfs_from<A, C, S, G>(graph: &A, sources: S, is_goal: G) -> Option<Path<A::Point>>
+where
+    A: AStar<C> + ?Sized,
+    A::Point: Hash + Eq + Clone,
+    C: Cost,
+    S: IntoIterator<Item = A::Point>,
+    G: FnMut(&A::Point) -> bool,
+{
+    bfs(
+        sources,
+        |point| unweighted_neighbours(graph, point),
+        is_goal,
+    )
+}
+
+/// Like [`bfs_all`], but uses the neighbours of an [`AStar`] implementation. The distances
+/// between neighbours are ignored, every step has cost 1.
+pub fn bfs_all_from<A, C, S>(graph: &A, sources: S) -> DistanceMap<A::Point>
+where
+    A: AStar<C> + ?Sized,
+    A::Point: Hash + Eq + Clone,
+    C: Cost,
+    S: IntoIterator<Item = A::Point>,
+{
+    bfs_all(sources, |point| unweighted_neighbours(graph, point))
+}
+
+/// Like [`dijkstra`], but uses the neighbours of an [`AStar`] implementation.
+pub fn dijkstra_from<A, C, S, G>(graph: &A, sources: S, is_goal: G) -> Option<Path<A::Point, C>>
+where
+    A: AStar<C> + ?Sized,
+    A::Point: Hash + Eq + Clone,
+    C: Cost,
+    S: IntoIterator<Item = A::Point>,
+    G: FnMut(&A::Point) -> bool,
+{
+    dijkstra(sources, |point| graph.get_neighbours(point), is_goal)
+}
+
+/// Like [`dijkstra_all`], but uses the neighbours of an [`AStar`] implementation.
+pub fn dijkstra_all_from<A, C, S>(graph: &A, sources: S) -> DistanceMap<A::Point, C>
+where
+    A: AStar<C> + ?Sized,
+    A::Point: Hash + Eq + Clone,
+    C: Cost,
+    S: IntoIterator<Item = A::Point>,
+{
+    dijkstra_all(sources, |point| graph.get_neighbours(point))
+}
+
+fn unweighted_neighbours<A, C>(graph: &A, point: &A::Point) -> impl Iterator<Item = A::Point>
+where
+    A: AStar<C> + ?Sized,
+    C: Cost,
+{
+    graph
+        .get_neighbours(point)
+        .into_iter()
+        .map(|(_, neighbour)| neighbour)
+}
+
+fn run_bfs<P, S, F, N, G>(
+    sources: S,
+    mut neighbours: F,
+    mut is_goal: Option<G>,
+) -> (DistanceMap<P>, Option<P>)
+where
+    P: Hash + Eq + Clone,
+    S: IntoIterator<Item = P>,
+    F: FnMut(&P) -> N,
+    N: IntoIterator<Item = P>,
+    G: FnMut(&P) -> bool,
+{
+    let mut map = DistanceMap::new();
+    let mut queue = VecDeque::new();
+    for source in sources {
+        if !map.contains(&source) {
+            map.distances.insert(source.clone(), 0);
+            queue.push_back((0, source));
+        }
+    }
+
+    while let Some((distance, current)) = queue.pop_front() {
+        if is_goal.as_mut().is_some_and(|is_goal| is_goal(&current)) {
+            return (map, Some(current));
+        }
+
+        map.expanded += 1;
+        for neighbour in neighbours(&current) {
+            if !map.contains(&neighbour) {
+                map.distances.insert(neighbour.clone(), distance + 1);
+                map.predecessors.insert(neighbour.clone(), current.clone());
+                queue.push_back((distance + 1, neighbour));
+            }
+        }
+    }
+
+    (map, None)
+}
+
+fn run_dijkstra<P, C, S, F, N, G>(
+    sources: S,
+    mut neighbours: F,
+    mut is_goal: Option<G>,
+) -> (DistanceMap<P, C>, Option<P>)
+where
+    P: Hash + Eq + Clone,
+    C: Cost,
+    S: IntoIterator<Item = P>,
+    F: FnMut(&P) -> N,
+    N: IntoIterator<Item = (C, P)>,
+    G: FnMut(&P) -> bool,
+{
+    let mut map = DistanceMap::new();
+    let mut open_set = BinaryHeap::new();
+    for source in sources {
+        if !map.contains(&source) {
+            map.distances.insert(source.clone(), C::ZERO);
+            open_set.push(SortablePoint {
+                point: source,
+                f_score: C::ZERO,
+            });
+        }
+    }
+
+    while let Some(SortablePoint {
+        point: current,
+        f_score: distance,
+    }) = open_set.pop()
+    {
+        if map.distance(&current).is_some_and(|d| d < distance) {
+            // a cheaper path to this point was found after it was added to the open set
+            continue;
+        }
+        if is_goal.as_mut().is_some_and(|is_goal| is_goal(&current)) {
+            return (map, Some(current));
+        }
+
+        map.expanded += 1;
+        for (cost, neighbour) in neighbours(&current) {
+            let Some(distance) = crate::cost::extend(distance, cost) else {
+                // a path that does not fit in the cost type is never the shortest path
+                continue;
+            };
+            if map.distance(&neighbour).is_none_or(|d| distance < d) {
+                map.distances.insert(neighbour.clone(), distance);
+                map.predecessors.insert(neighbour.clone(), current.clone());
+                open_set.push(SortablePoint {
+                    point: neighbour,
+                    f_score: distance,
+                });
+            }
+        }
+    }
+
+    (map, None)
+}
+
+/// Follows the predecessors back from `end`, and returns the points in the order in which they are
+/// visited when walking from the start to `end`.
+pub(crate) fn reconstruct_path<P>(predecessors: &HashMap<P, P>, end: &P) -> Vec<P>
+where
+    P: Hash + Eq + Clone,
+{
+    let mut point = end;
+    let mut path = vec![point.clone()];
+    while let Some(p) = predecessors.get(point) {
+        point = p;
+        path.push(p.clone());
+    }
+    path.reverse();
+    path
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A 5x5 grid with a wall in the middle column, except for the bottom row.
+    fn grid_neighbours(&(x, y): &(i32, i32)) -> Vec<(i32, i32)> {
+        [(x - 1, y), (x + 1, y), (x, y - 1), (x, y + 1)]
+            .into_iter()
+            .filter(|&(x, y)| (0..5).contains(&x) && (0..5).contains(&y))
+            .filter(|&(x, y)| x != 2 || y == 4)
+            .collect()
+    }
+
+    /// The same grid as [`grid_neighbours`], where moving down costs 2.
+    struct Grid;
+
+    impl AStar<u32> for Grid {
+        type Point = (i32, i32);
+        type EndPoint = (i32, i32);
+
+        fn distance_score(&self, &(xa, ya): &Self::Point, &(xb, yb): &Self::EndPoint) -> u32 {
+            xa.abs_diff(xb) + ya.abs_diff(yb)
+        }
+
+        fn get_neighbours(&self, point: &Self::Point) -> Vec<(u32, Self::Point)> {
+            grid_neighbours(point)
+                .into_iter()
+                .map(|neighbour| (if neighbour.1 > point.1 { 2 } else { 1 }, neighbour))
+                .collect()
+        }
+    }
+
+    #[test]
+    fn test_bfs() {
+        let path = bfs([(0, 0)], grid_neighbours, |&p| p == (4, 0)).unwrap();
+
+        assert_eq!(path.cost, 12);
+        assert_eq!(path.points.len(), 13);
+        assert_eq!(path.points.first(), Some(&(0, 0)));
+        assert_eq!(path.points.last(), Some(&(4, 0)));
+    }
+
+    #[test]
+    fn test_bfs_multiple_sources() {
+        let path = bfs([(0, 0), (1, 3)], grid_neighbours, |&p| p == (4, 4)).unwrap();
+
+        assert_eq!(path.cost, 4);
+        assert_eq!(path.points.first(), Some(&(1, 3)));
+    }
+
+    #[test]
+    fn test_bfs_unreachable() {
+        let path = bfs([(0, 0)], grid_neighbours, |&p| p == (2, 0));
+
+        assert_eq!(path, None);
+    }
+
+    #[test]
+    fn test_bfs_all() {
+        let distances = bfs_all([(0, 0)], grid_neighbours);
+
+        assert_eq!(distances.len(), 21);
+        assert_eq!(distances.distance(&(0, 0)), Some(0));
+        assert_eq!(distances.distance(&(4, 0)), Some(12));
+        assert_eq!(distances.distance(&(2, 0)), None);
+        assert_eq!(distances.predecessor(&(0, 0)), None);
+        assert_eq!(distances.path_to(&(2, 4)).unwrap().cost, 6);
+        assert_eq!(distances.path_to(&(2, 4)).unwrap().expanded, 21);
+    }
+
+    #[test]
+    fn test_dijkstra() {
+        // going right is cheap, going down is expensive
+        let neighbours = |&(x, y): &(i32, i32)| {
+            grid_neighbours(&(x, y))
+                .into_iter()
+                .map(move |(nx, ny)| (if ny != y { 10 } else { 1 }, (nx, ny)))
+        };
+
+        let path = dijkstra([(0, 0)], neighbours, |&p| p == (4, 0)).unwrap();
+        assert_eq!(path.cost, 84);
+        assert_eq!(path.points.len(), 13);
+
+        let distances = dijkstra_all([(0, 0)], neighbours);
+        assert_eq!(distances.distance(&(4, 0)), Some(84));
+        assert_eq!(distances.distance(&(2, 4)), Some(42));
+        assert_eq!(distances.path_to(&(4, 0)).unwrap().points, path.points);
+    }
+
+    #[test]
+    fn test_dijkstra_overflowing_costs() {
+        // the path to 3 via 1 does not fit in a u8, but the path via 2 does
+        let neighbours = |&p: &u8| match p {
+            0 => vec![(200u8, 1), (1, 2)],
+            1 => vec![(100, 3)],
+            2 => vec![(1, 3)],
+            _ => vec![],
+        };
+        let distances = dijkstra_all([0], neighbours);
+        assert_eq!(distances.distance(&0), Some(0));
+        assert_eq!(distances.distance(&1), Some(200));
+        assert_eq!(distances.distance(&2), Some(1));
+        assert_eq!(distances.distance(&3), Some(2));
+        assert_eq!(distances.predecessor(&3), Some(&2));
+    }
+
+    #[test]
+    #[should_panic(expected = "Found an edge with a negative cost: -1")]
+    fn test_dijkstra_negative_cost() {
+        let neighbours = |&p: &u8| match p {
+            0 => vec![(1, 1)],
+            1 => vec![(-1, 2)],
+            _ => vec![],
+        };
+        dijkstra_all([0], neighbours);
+    }
+
+    #[test]
+    fn test_dijkstra_all_multiple_sources() {
+        let neighbours = |&p: &u32| [(1, p + 1), (1, p.saturating_sub(1))];
+        let distances = dijkstra_all([0, 10], |&p| {
+            neighbours(&p).into_iter().filter(|&(_, n)| n <= 10)
+        });
+
+        assert_eq!(distances.distance(&3), Some(3));
+        assert_eq!(distances.distance(&7), Some(3));
+        assert_eq!(distances.path_to(&8).unwrap().points, vec![10, 9, 8]);
+    }
+
+    #[test]
+    fn test_searches_from_astar() {
+        let path = bfs_from(&Grid, [(0, 0)], |&p| p == (4, 0)).unwrap();
+        assert_eq!(path.cost, 12);
+
+        let distances = bfs_all_from(&Grid, [(0, 0)]);
+        assert_eq!(distances.distance(&(4, 0)), Some(12));
+
+        let path = dijkstra_from(&Grid, [(0, 0)], |&p| p == (4, 0)).unwrap();
+        assert_eq!(path.cost, 16);
+        assert_eq!(path.points.len(), 13);
+
+        let distances = dijkstra_all_from(&Grid, [(0, 0)]);
+        assert_eq!(distances.distance(&(4, 0)), Some(16));
+        assert_eq!(
+            Grid.find_path(&(0, 0), &(4, 0)).map(|path| path.cost),
+            Some(16)
+        );
+    }
+}
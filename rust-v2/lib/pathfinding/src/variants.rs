@@ -0,0 +1,242 @@
+//! Variants of the searches on [`AStar`] for huge state spaces. IDA* trades speed for memory,
+//! because it does not need to keep a predecessor for every point that was ever reached. The
+//! bidirectional search does keep these, but usually reaches far fewer points.
+
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+
+use crate::cost::{self, Cost};
+use crate::search::Path;
+use crate::AStar;
+
+/// The result of a single depth-first pass of IDA*.
+enum Outcome<C> {
+    /// A goal was found with the given cost.
+    Found(C),
+    /// No goal was found. Contains the lowest f-score that exceeded the threshold, if any.
+    Exceeded(Option<C>),
+}
+
+pub(crate) fn ida_star<A, C, G>(
+    astar: &A,
+    start: &A::Point,
+    end: &A::EndPoint,
+    is_goal: G,
+    max_cost: Option<C>,
+) -> Option<Path<A::Point, C>>
+where
+    A: AStar<C> + ?Sized,
+    C: Cost,
+    A::Point: Hash + Eq + Clone,
+    G: Fn(&A::Point) -> bool,
+{
+    let mut search = DepthFirst {
+        astar,
+        end,
+        is_goal,
+        path: vec![start.clone()],
+        on_path: HashSet::from([start.clone()]),
+        expanded: 0,
+    };
+
+    let mut threshold = astar.distance_score(start, end);
+    loop {
+        if max_cost.is_some_and(|max| threshold > max) {
+            return None;
+        }
+
+        match search.search(C::ZERO, threshold) {
+            Outcome::Found(cost) => {
+                return Some(Path {
+                    cost,
+                    points: search.path,
+                    expanded: search.expanded,
+                })
+            }
+            Outcome::Exceeded(Some(next)) => threshold = next,
+            Outcome::Exceeded(None) => return None,
+        }
+    }
+}
+
+struct DepthFirst<'a, A: AStar<C> + ?Sized, C: Cost, G> {
+    astar: &'a A,
+    end: &'a A::EndPoint,
+    is_goal: G,
+    path: Vec<A::Point>,
+    on_path: HashSet<A::Point>,
+    expanded: usize,
+}
+
+impl<A, C, G> DepthFirst<'_, A, C, G>
+where
+    A: AStar<C> + ?Sized,
+    C: Cost,
+    A::Point: Hash + Eq + Clone,
+    G: Fn(&A::Point) -> bool,
+{
+    /// Explores all paths that extend the current path, as long as their f-score does not exceed
+    /// `threshold`. If a goal is found, the path to it is left in `self.path`.
+    fn search(&mut self, cost: C, threshold: C) -> Outcome<C> {
+        let current = self.path[self.path.len() - 1].clone();
+        let Some(f_score) = cost.checked_add(self.astar.distance_score(&current, self.end)) else {
+            // a path that does not fit in the cost type is never the shortest path
+            return Outcome::Exceeded(None);
+        };
+        if f_score > threshold {
+            return Outcome::Exceeded(Some(f_score));
+        }
+        if (self.is_goal)(&current) {
+            return Outcome::Found(cost);
+        }
+
+        self.expanded += 1;
+        let mut lowest_exceeding = None;
+        for (d, neighbour) in self.astar.get_neighbours(&current) {
+            if self.on_path.contains(&neighbour) {
+                continue;
+            }
+            let Some(cost) = cost::extend(cost, d) else {
+                continue;
+            };
+
+            self.on_path.insert(neighbour.clone());
+            self.path.push(neighbour);
+            match self.search(cost, threshold) {
+                Outcome::Found(cost) => return Outcome::Found(cost),
+                Outcome::Exceeded(Some(f)) => {
+                    lowest_exceeding = Some(lowest_exceeding.map_or(f, |l: C| l.min(f)));
+                }
+                Outcome::Exceeded(None) => {}
+            }
+            let neighbour = self.path.pop().unwrap();
+            self.on_path.remove(&neighbour);
+        }
+
+        Outcome::Exceeded(lowest_exceeding)
+    }
+}
+
+pub(crate) fn bidirectional_bfs<A, C>(
+    astar: &A,
+    start: &A::Point,
+    end: &A::Point,
+) -> Option<Path<A::Point>>
+where
+    A: AStar<C> + ?Sized,
+    A::Point: Hash + Eq + Clone,
+    C: Cost,
+{
+    let mut expanded = 0;
+    if start == end {
+        return Some(Path {
+            cost: 0,
+            points: vec![start.clone()],
+            expanded,
+        });
+    }
+
+    let mut forwards = Frontier::new(start);
+    let mut backwards = Frontier::new(end);
+    loop {
+        // always grow the smallest layer
+        let grow_forwards = forwards.layer.len() <= backwards.layer.len();
+        let (growing, other) = if grow_forwards {
+            (&mut forwards, &backwards)
+        } else {
+            (&mut backwards, &forwards)
+        };
+        if growing.layer.is_empty() {
+            // every point that can be reached from this side has been visited
+            return None;
+        }
+
+        expanded += growing.layer.len();
+        if grow_forwards {
+            growing.grow(|point| {
+                astar
+                    .get_neighbours(point)
+                    .into_iter()
+                    .map(|(_, neighbour)| neighbour)
+                    .collect()
+            });
+        } else {
+            growing.grow(|point| astar.get_predecessors(point));
+        }
+
+        let meeting = growing
+            .layer
+            .iter()
+            .filter_map(|point| Some((other.depth_of(point)?, point)))
+            .min_by_key(|&(depth, _)| depth);
+        if let Some((depth, point)) = meeting {
+            let cost = growing.depth + depth;
+            let point = point.clone();
+            let mut points = forwards.path_from_source(&point);
+            points.pop();
+            let mut to_end = backwards.path_from_source(&point);
+            to_end.reverse();
+            points.extend(to_end);
+
+            return Some(Path {
+                cost,
+                points,
+                expanded,
+            });
+        }
+    }
+}
+
+/// One side of a bidirectional search. For every visited point, the number of steps from the
+/// source and the point from which it was reached are stored. Since no point is visited twice,
+/// the search ends once every point that can be reached from the source has been visited.
+struct Frontier<P> {
+    visited: HashMap<P, (u64, Option<P>)>,
+    layer: Vec<P>,
+    depth: u64,
+}
+
+impl<P: Hash + Eq + Clone> Frontier<P> {
+    fn new(source: &P) -> Frontier<P> {
+        Frontier {
+            visited: HashMap::from([(source.clone(), (0, None))]),
+            layer: vec![source.clone()],
+            depth: 0,
+        }
+    }
+
+    /// Replaces the current layer with the unvisited points that can be reached from it in one
+    /// step, where `step` lists the points that can be reached from a point.
+    fn grow<F>(&mut self, mut step: F)
+    where
+        F: FnMut(&P) -> Vec<P>,
+    {
+        self.depth += 1;
+        let mut next = vec![];
+        for point in &self.layer {
+            for neighbour in step(point) {
+                if !self.visited.contains_key(&neighbour) {
+                    self.visited
+                        .insert(neighbour.clone(), (self.depth, Some(point.clone())));
+                    next.push(neighbour);
+                }
+            }
+        }
+        self.layer = next;
+    }
+
+    /// The number of steps between the source and `point`, if it has been visited.
+    fn depth_of(&self, point: &P) -> Option<u64> {
+        self.visited.get(point).map(|&(depth, _)| depth)
+    }
+
+    /// The points on the path from the source to `point`, which must have been visited.
+    fn path_from_source(&self, point: &P) -> Vec<P> {
+        let mut path = vec![point.clone()];
+        while let Some((_, Some(previous))) = self.visited.get(&path[path.len() - 1]) {
+            path.push(previous.clone());
+        }
+        path.reverse();
+        path
+    }
+}
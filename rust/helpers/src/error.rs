@@ -34,39 +34,122 @@ impl<T> WithOrExit<T> for Option<T> {
     }
 }
 
-/// Generic parsing error.
+/// Generic parsing error. The [`ErrorKind`] describes what went wrong, so callers (and tests) can
+/// inspect it without depending on the exact wording of the message.
 #[derive(Debug, Clone, Eq, PartialEq)]
-pub struct ParseError(pub String);
+pub struct ParseError {
+    kind: ErrorKind,
+}
+
+/// The different kinds of parse errors.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum ErrorKind {
+    /// The input ended, while `expected` was still expected.
+    UnexpectedEnd { expected: String },
+    /// The input contains `found`, where `expected` was expected.
+    UnexpectedText { expected: String, found: String },
+    /// A number could not be parsed. The text is empty if it is not known (e.g. when the error
+    /// was converted from a [`ParseIntError`](std::num::ParseIntError)).
+    InvalidNumber { text: String, reason: String },
+    /// A value could not be parsed. The text is empty if it is not known.
+    InvalidValue { text: String, reason: String },
+    /// A character in a grid does not represent a valid cell. The position is given as
+    /// `(row, col)`.
+    InvalidCell { ch: char, pos: (usize, usize) },
+    /// The input could not be read.
+    Io(String),
+    /// The input is well-formed, but does not make sense.
+    Semantic(String),
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ErrorKind::UnexpectedEnd { expected } => {
+                write!(f, "Unexpected end of input, expected {}.", expected)
+            }
+            ErrorKind::UnexpectedText { expected, found } => {
+                write!(f, "Expected {}, found {}", expected, found)
+            }
+            ErrorKind::InvalidNumber { text, reason } if !text.is_empty() => {
+                write!(
+                    f,
+                    "Could not parse number '{}' with error '{}'",
+                    text, reason
+                )
+            }
+            ErrorKind::InvalidValue { text, reason } if !text.is_empty() => {
+                write!(
+                    f,
+                    "Could not parse value '{}' with error '{}'",
+                    text, reason
+                )
+            }
+            ErrorKind::InvalidNumber { reason, .. } | ErrorKind::InvalidValue { reason, .. } => {
+                write!(f, "{}", reason)
+            }
+            ErrorKind::InvalidCell { ch, .. } => write!(f, "Invalid cell '{}'", ch),
+            ErrorKind::Io(message) | ErrorKind::Semantic(message) => write!(f, "{}", message),
+        }
+    }
+}
 
 impl ParseError {
+    /// Construct a new [`ErrorKind::Semantic`] parse error.
+    pub fn new(message: impl Into<String>) -> ParseError {
+        ParseError::from(ErrorKind::Semantic(message.into()))
+    }
+
     /// Produces a `ParseError` from a `&str`.
     #[deprecated(since = "0.6.1", note = "Please use the parse_error! macro instead.")]
     pub fn of(s: &str) -> ParseError {
-        ParseError(s.to_string())
+        ParseError::new(s)
+    }
+
+    /// What went wrong.
+    pub fn kind(&self) -> &ErrorKind {
+        &self.kind
+    }
+
+    /// The message describing what went wrong.
+    pub fn message(&self) -> String {
+        self.kind.to_string()
     }
 }
 
 impl fmt::Display for ParseError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", self.0)
+        write!(f, "{}", self.kind)
+    }
+}
+
+impl From<ErrorKind> for ParseError {
+    fn from(kind: ErrorKind) -> Self {
+        ParseError { kind }
     }
 }
 
 impl From<std::io::Error> for ParseError {
     fn from(err: std::io::Error) -> Self {
-        ParseError(format!("{:?}", err))
+        ParseError::from(ErrorKind::Io(err.to_string()))
     }
 }
 
 impl From<std::num::ParseIntError> for ParseError {
     fn from(err: std::num::ParseIntError) -> Self {
-        ParseError(format!("{:?}", err))
+        ParseError::from(ErrorKind::InvalidNumber {
+            text: String::new(),
+            reason: err.to_string(),
+        })
     }
 }
 
 impl From<std::char::ParseCharError> for ParseError {
     fn from(err: ParseCharError) -> Self {
-        ParseError(format!("{:?}", err))
+        ParseError::from(ErrorKind::InvalidValue {
+            text: String::new(),
+            reason: err.to_string(),
+        })
     }
 }
 
@@ -81,10 +164,10 @@ impl From<std::char::ParseCharError> for ParseError {
 #[macro_export]
 macro_rules! parse_error {
     ($err:expr) => {{
-        $crate::error::ParseError($err.to_string())
+        $crate::error::ParseError::new($err.to_string())
     }};
     ($err:expr, $($args:tt)*) => {{
-        $crate::error::ParseError(format!($err, $($args)*))
+        $crate::error::ParseError::new(format!($err, $($args)*))
     }};
 }
 
@@ -106,22 +189,19 @@ mod tests {
     #[test]
     fn test_macro_parse_error_simple() {
         let err = parse_error!["This is a simple error."];
-        assert_eq!(err, ParseError("This is a simple error.".to_string()));
+        assert_eq!(err, ParseError::new("This is a simple error."));
     }
 
     #[test]
     fn test_macro_parse_error_with_formatting() {
         let err = parse_error!["This is an error with {}.", "formatting"];
-        assert_eq!(
-            err,
-            ParseError("This is an error with formatting.".to_string())
-        );
+        assert_eq!(err, ParseError::new("This is an error with formatting."));
     }
 
     #[test]
     fn test_macro_err_parse_error_simple() {
         let err: Result<(), ParseError> = err_parse_error!["This is a simple error."];
-        assert_eq!(err, Err(ParseError("This is a simple error.".to_string())));
+        assert_eq!(err, Err(ParseError::new("This is a simple error.")));
     }
 
     #[test]
@@ -131,7 +211,38 @@ mod tests {
 
         assert_eq!(
             err,
-            Err(ParseError("This is an error with formatting.".to_string()))
+            Err(ParseError::new("This is an error with formatting."))
+        );
+    }
+
+    #[test]
+    fn test_error_kinds() {
+        assert_eq!(
+            parse_error!("Not a {}.", "pipe").kind(),
+            &ErrorKind::Semantic("Not a pipe.".to_string())
+        );
+        assert!(matches!(
+            ParseError::from("-1".parse::<u8>().unwrap_err()).kind(),
+            ErrorKind::InvalidNumber { .. }
+        ));
+        assert!(matches!(
+            ParseError::from("ab".parse::<char>().unwrap_err()).kind(),
+            ErrorKind::InvalidValue { .. }
+        ));
+
+        let err = ParseError::from(ErrorKind::UnexpectedText {
+            expected: "'y='".to_string(),
+            found: "'z=2'".to_string(),
+        });
+        assert_eq!(err.to_string(), "Expected 'y=', found 'z=2'");
+
+        let err = ParseError::from(std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            "input.txt: not found",
+        ));
+        assert_eq!(
+            err.kind(),
+            &ErrorKind::Io("input.txt: not found".to_string())
         );
     }
 }
@@ -26,7 +26,7 @@ impl FromStr for Part {
         match s {
             "part1" | "part 1" | "Part 1" | "Part1" => Ok(Part::One),
             "part2" | "part 2" | "Part 2" | "Part2" => Ok(Part::Two),
-            _ => Err(ParseError(format!("Invalid part: {}", s))),
+            _ => Err(ParseError::new(format!("Invalid part: {}", s))),
         }
     }
 }